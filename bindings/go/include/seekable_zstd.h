@@ -6,6 +6,10 @@
 #include <stdint.h>
 #include <stdlib.h>
 
+#if defined(__unix__) || defined(__APPLE__)
+#define SEEKABLE_ZSTD_UNIX 1
+#endif
+
 #define DEFAULT_FRAME_SIZE (256 * 1024)
 
 typedef struct SeekableDecoder SeekableDecoder;
@@ -49,6 +53,26 @@ int32_t seekable_read_range(struct SeekableDecoder *decoder,
                             uint8_t *out_data,
                             uintptr_t *out_len);
 
+#if defined(SEEKABLE_ZSTD_UNIX)
+/**
+ * Decompresses a range of bytes and writes it to a file descriptor.
+ *
+ * The range is written in chunks of at most one frame, so arbitrarily large
+ * ranges can be streamed to a file, pipe, or socket. The descriptor is not
+ * closed. Returns 0 on success. Unix only; the header declares it when
+ * `SEEKABLE_ZSTD_UNIX` is defined.
+ *
+ * # Safety
+ * `decoder` must be a valid pointer returned by `seekable_open`.
+ * `fd` must be an open file descriptor that is writable for the duration
+ * of the call.
+ */
+int32_t seekable_read_range_to_fd(struct SeekableDecoder *decoder,
+                                  uint64_t start,
+                                  uint64_t end,
+                                  int fd);
+#endif
+
 /**
  * Reads multiple ranges in parallel.
  *
@@ -40,3 +40,20 @@ test('reads range async correctly', async (t) => {
   const data2 = await reader.readRangeAsync(6, 11);
   t.is(data2.toString(), 'World');
 });
+
+test('returns archive stats', (t) => {
+  if (!fs.existsSync(fixturePath)) {
+    t.fail(`Fixture not found at ${fixturePath}`);
+    return;
+  }
+  const reader = new Reader(fixturePath);
+  const stats = reader.stats();
+  t.is(stats.path, fixturePath);
+  t.is(stats.sizeBytes, 11n);
+  t.is(stats.frameCount, 1);
+  t.is(stats.minFrameBytes, 11n);
+  t.is(stats.maxFrameBytes, 11n);
+  t.is(stats.avgFrameBytes, 11n);
+  t.true(stats.compressionRatio > 0);
+  t.deepEqual(reader.stats(), stats);
+});
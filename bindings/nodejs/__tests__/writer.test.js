@@ -0,0 +1,44 @@
+const test = require('ava');
+const { Reader, Writer } = require('../index.js');
+const fs = require('node:fs');
+const os = require('node:os');
+const path = require('node:path');
+
+function tempArchive(t) {
+  const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'seekable-zstd-'));
+  t.teardown(() => fs.rmSync(dir, { recursive: true, force: true }));
+  return path.join(dir, 'out.szst');
+}
+
+test('writes an archive readable by Reader', (t) => {
+  const archive = tempArchive(t);
+  const writer = new Writer(archive, 4);
+  writer.write(Buffer.from('Hello '));
+  writer.write(Buffer.from('World'));
+  const written = writer.close();
+  t.is(written, BigInt(fs.statSync(archive).size));
+
+  const reader = new Reader(archive);
+  t.is(reader.size, 11);
+  t.is(reader.frameCount, 3);
+  t.is(reader.readRange(0, 11).toString(), 'Hello World');
+});
+
+test('writes an archive asynchronously', async (t) => {
+  const archive = tempArchive(t);
+  const writer = new Writer(archive, undefined, 19);
+  await writer.writeAsync(Buffer.from('Hello '));
+  await writer.writeAsync(Buffer.from('World'));
+  const written = await writer.closeAsync();
+  t.is(written, BigInt(fs.statSync(archive).size));
+
+  const reader = new Reader(archive);
+  t.is(await reader.readRangeAsync(6, 11).then(String), 'World');
+});
+
+test('throws after close', (t) => {
+  const writer = new Writer(tempArchive(t));
+  writer.close();
+  t.throws(() => writer.write(Buffer.from('late')), { message: 'Writer is closed' });
+  t.throws(() => writer.close(), { message: 'Writer is closed' });
+});
@@ -3,15 +3,44 @@
 
 /* auto-generated by NAPI-RS */
 
+/** Archive statistics returned by `Reader.stats()`. */
+export interface ReaderStats {
+  path: string
+  sizeBytes: bigint
+  frameCount: number
+  minFrameBytes: bigint
+  maxFrameBytes: bigint
+  avgFrameBytes: bigint
+  /** Decompressed size divided by compressed size of the frames. */
+  compressionRatio: number
+}
 export declare class Reader {
   constructor(path: string)
   get size(): number
   get frameCount(): number
   readRange(start: number, end: number): Buffer
   readRangeAsync(start: number, end: number): Promise<Buffer>
+  /**
+   * Returns statistics about the archive's frames.
+   * The result is computed on the first call and cached.
+   */
+  stats(): ReaderStats
   /**
    * Closes the reader and releases resources.
    * After calling close(), any further operations will throw an error.
    */
   close(): void
 }
+/** Writes a seekable archive to a file. */
+export declare class Writer {
+  constructor(path: string, frameSize?: number | undefined | null, level?: number | undefined | null)
+  write(data: Buffer): void
+  writeAsync(data: Buffer): Promise<void>
+  /**
+   * Writes the seek table and closes the file.
+   * Returns the total size of the archive in bytes. After calling close(),
+   * any further operations will throw an error.
+   */
+  close(): bigint
+  closeAsync(): Promise<bigint>
+}
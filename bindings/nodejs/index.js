@@ -310,6 +310,7 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { Reader } = nativeBinding
+const { Reader, Writer } = nativeBinding
 
 module.exports.Reader = Reader
+module.exports.Writer = Writer
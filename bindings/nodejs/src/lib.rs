@@ -1,21 +1,56 @@
 #![deny(clippy::all)]
 
-use napi::bindgen_prelude::{Buffer, Error, Result, Status};
+use napi::bindgen_prelude::{BigInt, Buffer, Error, Result, Status};
 use napi_derive::napi;
-use seekable_zstd_core::ParallelDecoder;
+use seekable_zstd_core::encoder::DEFAULT_FRAME_SIZE;
+use seekable_zstd_core::{Encoder, ParallelDecoder};
+use std::fs::File;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// zstd's own default compression level.
+const DEFAULT_LEVEL: i32 = 3;
+
+/// Archive statistics returned by `Reader.stats()`.
+#[napi(object)]
+pub struct ReaderStats {
+    pub path: String,
+    pub size_bytes: BigInt,
+    pub frame_count: u32,
+    pub min_frame_bytes: BigInt,
+    pub max_frame_bytes: BigInt,
+    pub avg_frame_bytes: BigInt,
+    /// Decompressed size divided by compressed size of the frames.
+    pub compression_ratio: f64,
+}
+
+/// Cached statistics; archives are immutable, so these are computed once.
+#[derive(Clone, Copy)]
+struct Stats {
+    size_bytes: u64,
+    frame_count: u32,
+    min_frame_bytes: u64,
+    max_frame_bytes: u64,
+    compressed_bytes: u64,
+}
 
 #[napi]
 pub struct Reader {
     inner: Option<ParallelDecoder>,
+    path: String,
+    stats: OnceLock<Stats>,
 }
 
 #[napi]
 impl Reader {
     #[napi(constructor)]
     pub fn new(path: String) -> Result<Self> {
-        let inner = ParallelDecoder::open(path)
+        let inner = ParallelDecoder::open(&path)
             .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?;
-        Ok(Reader { inner: Some(inner) })
+        Ok(Reader {
+            inner: Some(inner),
+            path,
+            stats: OnceLock::new(),
+        })
     }
 
     #[napi(getter)]
@@ -84,6 +119,54 @@ impl Reader {
         .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?
     }
 
+    /// Returns statistics about the archive's frames.
+    /// The result is computed on the first call and cached.
+    #[napi]
+    pub fn stats(&self) -> Result<ReaderStats> {
+        let inner = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| Error::new(Status::GenericFailure, "Reader is closed"))?;
+
+        let stats = match self.stats.get() {
+            Some(stats) => *stats,
+            None => {
+                let seek_table = inner
+                    .seek_table()
+                    .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?;
+                let frame_sizes =
+                    (0..seek_table.frame_count()).filter_map(|i| seek_table.frame_size(i));
+                let stats = Stats {
+                    size_bytes: seek_table.size(),
+                    frame_count: seek_table.frame_count() as u32,
+                    min_frame_bytes: frame_sizes.clone().min().unwrap_or(0),
+                    max_frame_bytes: frame_sizes.max().unwrap_or(0),
+                    compressed_bytes: seek_table.compressed_size(),
+                };
+                *self.stats.get_or_init(|| stats)
+            }
+        };
+
+        let avg_frame_bytes = match stats.frame_count {
+            0 => 0,
+            n => stats.size_bytes / u64::from(n),
+        };
+        let compression_ratio = match stats.compressed_bytes {
+            0 => 0.0,
+            c => stats.size_bytes as f64 / c as f64,
+        };
+
+        Ok(ReaderStats {
+            path: self.path.clone(),
+            size_bytes: BigInt::from(stats.size_bytes),
+            frame_count: stats.frame_count,
+            min_frame_bytes: BigInt::from(stats.min_frame_bytes),
+            max_frame_bytes: BigInt::from(stats.max_frame_bytes),
+            avg_frame_bytes: BigInt::from(avg_frame_bytes),
+            compression_ratio,
+        })
+    }
+
     /// Closes the reader and releases resources.
     /// After calling close(), any further operations will throw an error.
     #[napi]
@@ -91,3 +174,81 @@ impl Reader {
         self.inner = None;
     }
 }
+
+type SharedEncoder = Arc<Mutex<Option<Encoder<'static, File>>>>;
+
+/// Writes a seekable archive to a file.
+#[napi]
+pub struct Writer {
+    inner: SharedEncoder,
+}
+
+#[napi]
+impl Writer {
+    #[napi(constructor)]
+    pub fn new(path: String, frame_size: Option<u32>, level: Option<i32>) -> Result<Self> {
+        let file =
+            File::create(&path).map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?;
+        let frame_size = frame_size.map_or(DEFAULT_FRAME_SIZE, |size| size as usize);
+        let encoder = Encoder::new_with_options(file, frame_size, level.unwrap_or(DEFAULT_LEVEL))
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?;
+        Ok(Writer {
+            inner: Arc::new(Mutex::new(Some(encoder))),
+        })
+    }
+
+    #[napi]
+    pub fn write(&self, data: Buffer) -> Result<()> {
+        write_shared(&self.inner, &data)
+    }
+
+    #[napi]
+    pub async fn write_async(&self, data: Buffer) -> Result<()> {
+        let inner = Arc::clone(&self.inner);
+        let data = data.to_vec();
+
+        // Offload compression to the blocking thread pool
+        napi::tokio::task::spawn_blocking(move || write_shared(&inner, &data))
+            .await
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?
+    }
+
+    /// Writes the seek table and closes the file.
+    /// Returns the total size of the archive in bytes. After calling close(),
+    /// any further operations will throw an error.
+    #[napi]
+    pub fn close(&self) -> Result<BigInt> {
+        close_shared(&self.inner)
+    }
+
+    #[napi]
+    pub async fn close_async(&self) -> Result<BigInt> {
+        let inner = Arc::clone(&self.inner);
+        napi::tokio::task::spawn_blocking(move || close_shared(&inner))
+            .await
+            .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?
+    }
+}
+
+fn write_shared(inner: &SharedEncoder, data: &[u8]) -> Result<()> {
+    let mut guard = inner
+        .lock()
+        .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?;
+    guard
+        .as_mut()
+        .ok_or_else(|| Error::new(Status::GenericFailure, "Writer is closed"))?
+        .write_all(data)
+        .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+}
+
+fn close_shared(inner: &SharedEncoder) -> Result<BigInt> {
+    let encoder = inner
+        .lock()
+        .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?
+        .take()
+        .ok_or_else(|| Error::new(Status::GenericFailure, "Writer is closed"))?;
+    let written = encoder
+        .finish()
+        .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))?;
+    Ok(BigInt::from(written))
+}
@@ -0,0 +1,127 @@
+//! Cold vs. warm page cache latency for `Decoder::read_range`.
+//!
+//! Each frame size gets its own archive on disk. The cold benchmarks evict
+//! the archive from the OS page cache with `posix_fadvise(POSIX_FADV_DONTNEED)`
+//! before every read (Linux only); the warm benchmarks read from an archive
+//! that is already cached. Criterion writes HTML plots to
+//! `target/criterion/read_range_cold` and `target/criterion/read_range_warm`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use seekable_zstd_core::{Decoder, Encoder};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+const ARCHIVE_SIZE: usize = 16 * 1024 * 1024;
+const READ_LEN: u64 = 4096;
+const FRAME_SIZES: [usize; 3] = [64 * 1024, 256 * 1024, 1024 * 1024];
+
+/// Deterministic, moderately compressible input.
+fn input_data() -> Vec<u8> {
+    let mut state: u32 = 0x1234_5678;
+    (0..ARCHIVE_SIZE)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            if i % 4 == 0 {
+                state.to_le_bytes()[0]
+            } else {
+                b'a' + (i % 26) as u8
+            }
+        })
+        .collect()
+}
+
+fn write_archive(data: &[u8], frame_size: usize) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "seekable-zstd-bench-{frame_size}-{}.szst",
+        std::process::id()
+    ));
+    let file = File::create(&path).expect("create bench archive");
+    let mut encoder = Encoder::with_frame_size(file, frame_size).expect("create encoder");
+    encoder.write_all(data).expect("compress bench data");
+    encoder.finish().expect("finish bench archive");
+    File::open(&path)
+        .and_then(|f| f.sync_all())
+        .expect("sync bench archive");
+    path
+}
+
+#[cfg(target_os = "linux")]
+fn evict_from_page_cache(path: &Path) {
+    use std::os::unix::io::AsRawFd;
+
+    let file = File::open(path).expect("open bench archive");
+    // SAFETY: `file` is an open descriptor for the duration of the call;
+    // offset and length of 0 cover the whole file.
+    let rc = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    assert_eq!(rc, 0, "posix_fadvise failed");
+}
+
+fn read_offset(frame_size: usize) -> u64 {
+    // Middle of the archive, straddling a frame boundary
+    let frames = ARCHIVE_SIZE / frame_size;
+    ((frames / 2) * frame_size) as u64 - READ_LEN / 2
+}
+
+fn bench_cache_behavior(c: &mut Criterion) {
+    let data = input_data();
+    let archives: Vec<(usize, PathBuf)> = FRAME_SIZES
+        .iter()
+        .map(|&frame_size| (frame_size, write_archive(&data, frame_size)))
+        .collect();
+
+    #[cfg(target_os = "linux")]
+    {
+        let mut cold = c.benchmark_group("read_range_cold");
+        for (frame_size, path) in &archives {
+            let start = read_offset(*frame_size);
+            cold.bench_with_input(
+                BenchmarkId::from_parameter(frame_size / 1024),
+                path,
+                |b, path| {
+                    b.iter_batched(
+                        || evict_from_page_cache(path),
+                        |()| {
+                            let mut decoder = Decoder::open(path).expect("open archive");
+                            decoder
+                                .read_range(start, start + READ_LEN)
+                                .expect("read range")
+                        },
+                        BatchSize::PerIteration,
+                    );
+                },
+            );
+        }
+        cold.finish();
+    }
+
+    let mut warm = c.benchmark_group("read_range_warm");
+    for (frame_size, path) in &archives {
+        // Alternate between two ranges a few frames apart, so each read
+        // decompresses instead of reusing the frames of the one before
+        let starts = [
+            read_offset(*frame_size),
+            read_offset(*frame_size) - 2 * *frame_size as u64,
+        ];
+        let mut decoder = Decoder::open(path).expect("open archive");
+        let mut next = 0;
+        warm.bench_function(BenchmarkId::from_parameter(frame_size / 1024), |b| {
+            b.iter(|| {
+                let start = starts[next];
+                next ^= 1;
+                decoder
+                    .read_range(start, start + READ_LEN)
+                    .expect("read range")
+            });
+        });
+    }
+    warm.finish();
+
+    for (_, path) in archives {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+criterion_group!(benches, bench_cache_behavior);
+criterion_main!(benches);
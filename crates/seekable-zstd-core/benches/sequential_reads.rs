@@ -0,0 +1,75 @@
+//! Many small `Decoder::read_range` calls against one archive.
+//!
+//! `sequential` reads 10,000 consecutive 1 KiB ranges, so almost every read
+//! falls in the frames the previous read already decompressed.
+//! `frame_per_read` reads the same 10,000 ranges in an order that moves to a
+//! different frame on every call, so each read resets the decoder and
+//! decompresses a frame, which is what every read cost before decoded
+//! frames were kept between calls.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use seekable_zstd_core::{Decoder, Encoder};
+use std::io::Cursor;
+
+const READ_LEN: u64 = 1024;
+const READS: u64 = 10_000;
+const FRAME_SIZE: usize = 256 * 1024;
+
+/// Deterministic, moderately compressible input.
+fn input_data() -> Vec<u8> {
+    let mut state: u32 = 0x1234_5678;
+    (0..READ_LEN * READS)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            if i % 4 == 0 {
+                state.to_le_bytes()[0]
+            } else {
+                b'a' + (i % 26) as u8
+            }
+        })
+        .collect()
+}
+
+fn bench_sequential_reads(c: &mut Criterion) {
+    let mut archive = Vec::new();
+    let mut encoder = Encoder::with_frame_size(&mut archive, FRAME_SIZE).expect("create encoder");
+    encoder
+        .write_all(&input_data())
+        .expect("compress bench data");
+    encoder.finish().expect("finish bench archive");
+
+    let reads_per_frame = FRAME_SIZE as u64 / READ_LEN;
+    let frames = READS.div_ceil(reads_per_frame);
+    let sequential: Vec<u64> = (0..READS).map(|i| i * READ_LEN).collect();
+    // Visit every frame once per round, one read each, so consecutive reads
+    // never share a frame
+    let frame_per_read: Vec<u64> = (0..reads_per_frame)
+        .flat_map(|round| (0..frames).map(move |frame| frame * reads_per_frame + round))
+        .filter(|read| *read < READS)
+        .map(|read| read * READ_LEN)
+        .collect();
+
+    let mut group = c.benchmark_group("read_range_10k_1kib");
+    group.sample_size(10);
+    for (name, offsets) in [
+        ("sequential", &sequential),
+        ("frame_per_read", &frame_per_read),
+    ] {
+        let mut decoder = Decoder::new(Cursor::new(archive.as_slice())).expect("open archive");
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &start in offsets {
+                    decoder
+                        .read_range(start, start + READ_LEN)
+                        .expect("read range");
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sequential_reads);
+criterion_main!(benches);
@@ -12,7 +12,18 @@ fn main() {
 
     // Only generate if the directory exists (to avoid errors in CI if structure is different)
     if output_file.parent().unwrap().exists() {
+        // Unix-only functions are wrapped in a guard the header defines itself
+        let mut config = cbindgen::Config::default();
+        config
+            .defines
+            .insert("unix".to_string(), "SEEKABLE_ZSTD_UNIX".to_string());
+        config.after_includes = Some(
+            "\n#if defined(__unix__) || defined(__APPLE__)\n#define SEEKABLE_ZSTD_UNIX 1\n#endif"
+                .to_string(),
+        );
+
         cbindgen::Builder::new()
+            .with_config(config)
             .with_crate(crate_dir)
             .with_language(cbindgen::Language::C)
             .with_include_guard("SEEKABLE_ZSTD_H")
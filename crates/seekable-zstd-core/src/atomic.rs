@@ -0,0 +1,188 @@
+use crate::encoder::Encoder;
+use crate::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Distinguishes the temporary files of encoders created by one process.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// An [`Encoder`] that only makes the archive visible once it is complete.
+///
+/// Data is written to a temporary file next to the target path. On
+/// [`finish`](Self::finish) the seek table is written, the file is synced,
+/// and it is renamed over the target in one step, so readers never see a
+/// partially written archive. If the encoder is dropped without finishing,
+/// the temporary file is removed; a crash can leave it behind, but the
+/// target path is untouched either way.
+///
+/// The rename uses `std::fs::rename`, which replaces an existing target
+/// atomically on Unix and uses `MoveFileExW` with
+/// `MOVEFILE_REPLACE_EXISTING` on Windows.
+pub struct AtomicEncoder {
+    encoder: Option<Encoder<'static, File>>,
+    /// A second handle on the temporary file, used to sync it before the rename.
+    file: Option<File>,
+    temp_path: PathBuf,
+    path: PathBuf,
+    committed: bool,
+}
+
+impl AtomicEncoder {
+    /// Creates a temporary file alongside `path` and starts encoding into it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` has no file name, if the temporary file
+    /// cannot be created, or if the encoder cannot be initialized.
+    pub fn create<P: AsRef<Path>>(path: P, frame_size: usize, level: i32) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let name = path
+            .file_name()
+            .ok_or_else(|| Error::Format(format!("{} has no file name", path.display())))?;
+        let temp_path = path.with_file_name(format!(
+            ".{}.{}.{}.tmp",
+            name.to_string_lossy(),
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        // Never reuse a file someone else is writing, such as one left with
+        // the same name by an earlier process with this pid
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)?;
+        let mut atomic = Self {
+            encoder: None,
+            file: Some(file.try_clone()?),
+            temp_path,
+            path,
+            committed: false,
+        };
+        atomic.encoder = Some(Encoder::new_with_options(file, frame_size, level)?);
+        Ok(atomic)
+    }
+
+    /// Returns the path of the temporary file being written.
+    #[must_use]
+    pub fn temp_path(&self) -> &Path {
+        &self.temp_path
+    }
+
+    /// Finishes the archive and moves it into place at the target path.
+    ///
+    /// Returns the total size of the archive in bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if finishing, syncing, or renaming fails. The
+    /// temporary file is removed and the target path is left unchanged.
+    pub fn finish(mut self) -> Result<u64, Error> {
+        let encoder = self
+            .encoder
+            .take()
+            .ok_or_else(|| Error::Format("encoder already finished".to_string()))?;
+        let written = encoder.finish()?;
+        if let Some(file) = self.file.take() {
+            file.sync_all()?;
+        }
+        fs::rename(&self.temp_path, &self.path)?;
+        self.committed = true;
+        Ok(written)
+    }
+
+    fn encoder(&mut self) -> io::Result<&mut Encoder<'static, File>> {
+        self.encoder
+            .as_mut()
+            .ok_or_else(|| io::Error::other("encoder already finished"))
+    }
+}
+
+impl Write for AtomicEncoder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.encoder()?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.encoder()?.flush()
+    }
+}
+
+impl Drop for AtomicEncoder {
+    fn drop(&mut self) {
+        if !self.committed {
+            // Close our handles first; Windows cannot remove an open file
+            drop(self.encoder.take());
+            drop(self.file.take());
+            let _ = fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+
+    #[test]
+    fn test_atomic_encoder() {
+        let path =
+            std::env::temp_dir().join(format!("seekable-zstd-atomic-{}.szst", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut encoder = AtomicEncoder::create(&path, 16, 3).unwrap();
+        encoder.write_all(b"Hello World, atomically.").unwrap();
+        let temp_path = encoder.temp_path().to_path_buf();
+        assert!(temp_path.exists());
+        assert!(!path.exists());
+
+        let written = encoder.finish().unwrap();
+        assert!(!temp_path.exists());
+        assert_eq!(written, fs::metadata(&path).unwrap().len());
+        let mut decoder = Decoder::open(&path).unwrap();
+        assert_eq!(
+            decoder.read_range(0, 24).unwrap(),
+            b"Hello World, atomically."
+        );
+        drop(decoder);
+
+        // Dropping without finishing leaves the existing archive untouched
+        let mut encoder = AtomicEncoder::create(&path, 16, 3).unwrap();
+        encoder.write_all(b"discarded").unwrap();
+        drop(encoder);
+        assert!(!temp_path.exists());
+        assert_eq!(written, fs::metadata(&path).unwrap().len());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_atomic_encoders() {
+        let path = std::env::temp_dir().join(format!(
+            "seekable-zstd-atomic-concurrent-{}.szst",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let mut first = AtomicEncoder::create(&path, 16, 3).unwrap();
+        let mut second = AtomicEncoder::create(&path, 16, 3).unwrap();
+        assert_ne!(first.temp_path(), second.temp_path());
+        first.write_all(b"first").unwrap();
+        second.write_all(b"second").unwrap();
+
+        // Dropping one leaves the other's temporary file alone
+        let first_temp = first.temp_path().to_path_buf();
+        drop(first);
+        assert!(!first_temp.exists());
+        assert!(second.temp_path().exists());
+
+        second.finish().unwrap();
+        let mut decoder = Decoder::open(&path).unwrap();
+        assert_eq!(decoder.read_range(0, 6).unwrap(), b"second");
+        drop(decoder);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,22 +1,308 @@
 use crate::error::Error;
+#[cfg(any(feature = "sha2", feature = "blake3", feature = "xxhash"))]
+use crate::hash::{HashAlgorithm, Hasher};
+use crate::metadata::ArchiveMetadata;
+use crate::seek_table::{FrameInfo, SeekTable};
+use std::cell::OnceCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Weight given to the newest sample in the decompression speed average.
+const SPEED_EMA_ALPHA: f64 = 0.2;
+
+/// Counters describing how a [`Decoder`] has been used.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DecoderStats {
+    /// Range reads served, including block and frame-aligned reads.
+    pub reads: u64,
+    /// Reads whose range covered whole frames exactly, returned without
+    /// copying out a sub-range.
+    pub exact_frame_reads: u64,
+    /// Exponential moving average of decompression throughput in MB/s
+    /// (10^6 bytes per second), or `None` before the first timed read.
+    pub decompression_speed_mb_per_sec: Option<f64>,
+    /// Reads served from the frames decompressed by the previous read,
+    /// without decompressing again.
+    pub frame_cache_hits: u64,
+}
+
+impl DecoderStats {
+    /// Folds one timed decompression into the speed average.
+    fn record_speed(&mut self, bytes: usize, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        if bytes == 0 || secs <= 0.0 {
+            return;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let sample = bytes as f64 / 1_000_000.0 / secs;
+        self.decompression_speed_mb_per_sec = Some(match self.decompression_speed_mb_per_sec {
+            Some(average) => average + SPEED_EMA_ALPHA * (sample - average),
+            None => sample,
+        });
+    }
+
+    /// Fraction of reads that hit the exact-frame path, or `0.0` before the
+    /// first read.
+    #[must_use]
+    pub fn exact_frame_hit_rate(&self) -> f64 {
+        if self.reads == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let rate = self.exact_frame_reads as f64 / self.reads as f64;
+        rate
+    }
+}
+
+/// Checksum of one frame's decompressed contents, from
+/// [`Decoder::read_range_with_checksums`].
+#[cfg(feature = "xxhash")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameChecksum {
+    pub frame_index: u64,
+    /// XXH64 (seed 0) of the frame's decompressed data. The low 32 bits
+    /// equal the content checksum zstd stores in frames written with
+    /// checksums enabled.
+    pub xxhash: u64,
+}
+
+/// Data returned by [`Decoder::read_range_partial`], with a flag for reads
+/// cut short by the end of the archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadRangeResult {
+    data: Vec<u8>,
+    truncated: bool,
+}
+
+impl ReadRangeResult {
+    /// Returns the decompressed bytes.
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns `true` if the requested end lay past the end of the archive,
+    /// so fewer bytes were returned than requested.
+    #[must_use]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns the decompressed bytes, consuming the result.
+    #[must_use]
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
 
 pub struct Decoder<'a, R: Read + Seek> {
     inner: zeekstd::Decoder<'a, R>,
+    position: u64,
+    metadata: OnceCell<ArchiveMetadata>,
+    stats: DecoderStats,
+    /// Frames decompressed by the last partial read.
+    last_frames: Option<DecodedFrames>,
+}
+
+/// Decompressed contents of one frame, or two adjacent ones.
+///
+/// Small sequential reads usually land in the frames the previous read just
+/// decompressed, so keeping them lets those reads skip the decoder reset and
+/// decompression entirely. Only reads spanning at most two frames are kept,
+/// so the buffer never grows past two frames.
+struct DecodedFrames {
+    first_frame: u32,
+    last_frame: u32,
+    /// Decompressed offset of the start of `first_frame`.
+    start: u64,
+    data: Vec<u8>,
 }
 
-impl<R: Read + Seek> Decoder<'_, R> {
+impl<'a, R: Read + Seek> Decoder<'a, R> {
     /// Creates a new `Decoder` from the given reader.
     ///
     /// # Errors
     ///
     /// Returns an error if the decoder cannot be initialized, for example if the
     /// input is not a valid zstd stream or seekable archive.
+    ///
+    /// Only the seek table itself is validated; see
+    /// [`new_verified`](Self::new_verified) for checks against the frame data.
+    ///
+    /// Any `Read + Seek` source works, including a block device opened with
+    /// `File::open("/dev/sdb1")`; nothing here stats the file or relies on
+    /// its metadata. The seek table is located relative to
+    /// `SeekFrom::End(0)`, so the archive must end exactly at the end of the
+    /// device. An archive copied onto a larger device needs a reader that
+    /// reports the archive's length as the end.
     pub fn new(reader: R) -> Result<Self, Error> {
         let inner = zeekstd::Decoder::new(reader).map_err(Error::from)?;
-        Ok(Self { inner })
+        Ok(Self::from_inner(inner))
+    }
+
+    /// Creates a new `Decoder` for an archive written with a dictionary.
+    ///
+    /// `dictionary` must be the one given to
+    /// [`Encoder::with_dictionary`](crate::Encoder::with_dictionary) when the
+    /// archive was written. Reading with a different dictionary, or with
+    /// [`new`](Self::new), fails with a zstd error or returns wrong data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dictionary cannot be loaded, or any error
+    /// [`new`](Self::new) would return.
+    pub fn new_with_dictionary(reader: R, dictionary: &[u8]) -> Result<Self, Error> {
+        let mut dctx = zstd_safe::DCtx::try_create()
+            .ok_or_else(|| Error::Zstd("Failed to create decompression context".to_string()))?;
+        // Loaded dictionaries stay active across the per-read resets
+        dctx.load_dictionary(dictionary)
+            .map_err(|code| Error::Zstd(zstd_safe::get_error_name(code).to_string()))?;
+        let inner = zeekstd::DecodeOptions::new(reader)
+            .dctx(dctx)
+            .into_decoder()
+            .map_err(Error::from)?;
+        Ok(Self::from_inner(inner))
+    }
+
+    /// Creates a new `Decoder` after checking the seek table against the archive.
+    ///
+    /// The seek table format carries no checksum of its own, so this checks
+    /// what can be checked before decompressing anything: the frames and the
+    /// seek table must account for the exact length of the archive, and every
+    /// frame must start with the zstd magic number. This costs one small read
+    /// per frame. [`new`](Self::new) skips these checks.
+    ///
+    /// Frames written with a zstd format feature this build does not know
+    /// (a reserved frame header bit, or a different magic number in the zstd
+    /// range) are reported here by frame index, instead of as an opaque
+    /// decompression error on the first read that touches them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Format` if the archive is inconsistent with its seek
+    /// table or a frame uses an unsupported zstd feature, or any error
+    /// [`new`](Self::new) would return.
+    pub fn new_verified(mut reader: R) -> Result<Self, Error> {
+        const ZSTD_MAGIC: u32 = 0xFD2F_B528;
+
+        let seek_table = SeekTable::from_reader(&mut reader)?;
+        let table = &seek_table.inner;
+
+        let archive_len = reader.seek(SeekFrom::End(0))?;
+        let frames_len = match table.num_frames() {
+            0 => 0,
+            n => table.frame_end_comp(n - 1).map_err(Error::from)?,
+        };
+        let table_len = table.clone().into_serializer().encoded_len() as u64;
+        if frames_len + table_len != archive_len {
+            return Err(Error::Format(format!(
+                "Seek table describes {} bytes but archive is {archive_len} bytes",
+                frames_len + table_len
+            )));
+        }
+
+        // Magic number plus the frame header descriptor
+        let mut header = [0u8; 5];
+        for index in 0..table.num_frames() {
+            let offset = table.frame_start_comp(index).map_err(Error::from)?;
+            reader.seek(SeekFrom::Start(offset))?;
+            reader.read_exact(&mut header)?;
+            let magic = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+            let unsupported = if magic == ZSTD_MAGIC {
+                // Bit 3 is reserved for future format extensions
+                (header[4] & 0x08 != 0).then_some(u32::from(header[4]))
+            } else if magic & 0xFFFF_FF00 == ZSTD_MAGIC & 0xFFFF_FF00 {
+                // Other magic numbers in the zstd range are legacy or newer formats
+                Some(magic)
+            } else {
+                return Err(Error::Format(format!(
+                    "Frame {index} at offset {offset} is not a zstd frame"
+                )));
+            };
+            if let Some(feature) = unsupported {
+                return Err(Error::Format(format!(
+                    "frame {index} uses unsupported zstd feature: {feature:#06X}; \
+                     a newer version of seekable-zstd may be able to read it"
+                )));
+            }
+        }
+
+        let inner = zeekstd::DecodeOptions::new(reader)
+            .seek_table(seek_table.inner)
+            .into_decoder()
+            .map_err(Error::from)?;
+        Ok(Self::from_inner(inner))
+    }
+
+    /// Creates a new `Decoder` using an already parsed seek table.
+    ///
+    /// This skips reading the seek table from `reader`. The table must
+    /// describe this exact archive; in release builds a mismatched table
+    /// makes reads fail or return wrong data. Debug builds still parse the
+    /// archive's own seek table and compare the two.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the decoder cannot be initialized. Debug builds
+    /// also return `Error::Format` if `seek_table` does not match the
+    /// archive.
+    pub fn from_reader_and_seek_table(reader: R, seek_table: &SeekTable) -> Result<Self, Error> {
+        #[cfg(debug_assertions)]
+        let reader = {
+            let mut reader = reader;
+            if !SeekTable::from_reader(&mut reader)?.validate_against(seek_table) {
+                return Err(Error::Format(
+                    "Seek table does not match the archive".to_string(),
+                ));
+            }
+            reader
+        };
+        Self::from_reader_and_trusted_seek_table(reader, seek_table)
+    }
+
+    /// Like [`from_reader_and_seek_table`](Self::from_reader_and_seek_table),
+    /// without the debug-build check, for callers that already checked
+    /// `seek_table` against this archive.
+    pub(crate) fn from_reader_and_trusted_seek_table(
+        reader: R,
+        seek_table: &SeekTable,
+    ) -> Result<Self, Error> {
+        let inner = zeekstd::DecodeOptions::new(reader)
+            .seek_table(seek_table.inner.clone())
+            .into_decoder()
+            .map_err(Error::from)?;
+        Ok(Self::from_inner(inner))
+    }
+
+    /// Returns a copy of the archive's seek table.
+    #[must_use]
+    pub fn seek_table(&self) -> SeekTable {
+        SeekTable {
+            inner: (*self.inner).clone(),
+        }
+    }
+
+    /// Returns the position of every frame, in order.
+    ///
+    /// See [`SeekTable::frames`].
+    #[must_use]
+    pub fn frames(&self) -> Vec<FrameInfo> {
+        self.seek_table().frames()
+    }
+
+    fn from_inner(inner: zeekstd::Decoder<'a, R>) -> Self {
+        Self {
+            inner,
+            position: 0,
+            metadata: OnceCell::new(),
+            stats: DecoderStats::default(),
+            last_frames: None,
+        }
     }
 
     #[must_use]
@@ -29,31 +315,207 @@ impl<R: Read + Seek> Decoder<'_, R> {
         self.inner.frame_end_decomp(num_frames - 1).unwrap_or(0)
     }
 
+    /// Returns the total size of the archive in bytes, frames and seek table
+    /// included.
+    ///
+    /// Computed from the seek table, so it needs no `stat` call and works
+    /// the same for files and in-memory buffers. For a well-formed archive
+    /// it equals the length of the underlying reader; together with
+    /// [`size`](Self::size) it gives the compression ratio.
+    #[must_use]
+    pub fn size_compressed(&self) -> u64 {
+        let num_frames = self.inner.num_frames();
+        let frames_len = if num_frames == 0 {
+            0
+        } else {
+            self.inner.frame_end_comp(num_frames - 1).unwrap_or(0)
+        };
+        let table_len = (*self.inner).clone().into_serializer().encoded_len() as u64;
+        frames_len + table_len
+    }
+
     #[must_use]
     pub fn frame_count(&self) -> u64 {
         u64::from(self.inner.num_frames())
     }
 
-    /// Reads data into `buf` starting at `offset`.
+    /// Returns how many frames a read of `start..end` would decompress.
+    ///
+    /// Uses two binary searches on the seek table, so callers can estimate
+    /// the cost of a read (for example, to choose between this decoder and
+    /// `ParallelDecoder`) without decompressing anything. `end` is clamped
+    /// to the archive size, and an empty or out-of-bounds range spans `0`
+    /// frames.
+    #[must_use]
+    pub fn frame_count_in_range(&self, start: u64, end: u64) -> u64 {
+        let end = end.min(self.size());
+        if start >= end {
+            return 0;
+        }
+        let start_frame = self.inner.frame_index_decomp(start);
+        let end_frame = self.inner.frame_index_decomp(end - 1);
+        u64::from(end_frame - start_frame) + 1
+    }
+
+    /// Returns size, frame, and seek table information in one struct.
+    ///
+    /// Everything is derived from the in-memory seek table; no frame data is
+    /// read. The result is computed on the first call and cached.
+    ///
+    /// # Errors
+    ///
+    /// Currently infallible; the `Result` leaves room for metadata that
+    /// requires reading the archive.
+    pub fn metadata(&self) -> Result<ArchiveMetadata, Error> {
+        Ok(*self
+            .metadata
+            .get_or_init(|| ArchiveMetadata::from_seek_table(&self.seek_table())))
+    }
+
+    /// Returns the number of frames per decompressed frame size bucket.
+    ///
+    /// Bucket `i` counts frames whose decompressed size falls in
+    /// `[i * bucket_size_bytes, (i + 1) * bucket_size_bytes)`. The result is
+    /// empty if the archive has no frames or `bucket_size_bytes` is zero.
+    #[must_use]
+    pub fn frame_histogram(&self, bucket_size_bytes: u64) -> Vec<u64> {
+        if bucket_size_bytes == 0 {
+            return Vec::new();
+        }
+
+        let mut histogram = Vec::new();
+        for index in 0..self.inner.num_frames() {
+            let Ok(frame_size) = self.inner.frame_size_decomp(index) else {
+                continue;
+            };
+            let Ok(bucket) = usize::try_from(frame_size / bucket_size_bytes) else {
+                continue;
+            };
+            if bucket >= histogram.len() {
+                histogram.resize(bucket + 1, 0);
+            }
+            histogram[bucket] += 1;
+        }
+        histogram
+    }
+
+    /// Estimates how random the data in a frame is from its compression ratio.
+    ///
+    /// Returns the frame's compressed size divided by its decompressed size,
+    /// read from the seek table without decompressing anything. Values near
+    /// `0.0` mean highly compressible data; values near `1.0` mean the data
+    /// barely compressed, as with encrypted or already-compressed content.
+    /// Frame headers can push tiny frames past `1.0`, so the result is
+    /// capped there. An empty frame reports `0.0`. This is a rough proxy,
+    /// useful for spotting anomalies, not a true entropy measurement.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `frame_index` is out of range.
+    pub fn frame_entropy_estimate(&self, frame_index: u64) -> Result<f64, Error> {
+        let index = u32::try_from(frame_index)
+            .ok()
+            .filter(|index| *index < self.inner.num_frames())
+            .ok_or_else(|| Error::Format(format!("Frame {frame_index} out of range")))?;
+        let compressed = self.inner.frame_size_comp(index).map_err(Error::from)?;
+        let decompressed = self.inner.frame_size_decomp(index).map_err(Error::from)?;
+        if decompressed == 0 {
+            return Ok(0.0);
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = compressed as f64 / decompressed as f64;
+        Ok(ratio.min(1.0))
+    }
+
+    /// Returns usage counters accumulated since the decoder was created.
+    #[must_use]
+    pub fn stats(&self) -> DecoderStats {
+        self.stats
+    }
+
+    /// Returns the measured decompression throughput in MB/s.
+    ///
+    /// Each read is timed, and the estimate is an exponential moving average
+    /// over those reads, so it follows changes in data or machine load.
+    /// Returns `None` before the first read. Callers can use it to choose
+    /// between sequential and parallel decoding for large ranges.
+    #[must_use]
+    pub fn estimated_decompression_speed_mb_per_sec(&self) -> Option<f64> {
+        self.stats.decompression_speed_mb_per_sec
+    }
+
+    /// Returns the current position in the decompressed stream.
     ///
-    /// Returns the number of bytes read.
+    /// This is the end offset of the data returned by the most recent read,
+    /// or the offset set by [`seek_to`](Self::seek_to).
+    #[must_use]
+    pub fn current_position(&self) -> u64 {
+        self.position
+    }
+
+    /// Sets the current position in the decompressed stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pos` is beyond the end of the archive.
+    pub fn seek_to(&mut self, pos: u64) -> Result<(), Error> {
+        let size = self.size();
+        if pos > size {
+            return Err(Error::OutOfBounds { offset: pos, size });
+        }
+        self.position = pos;
+        Ok(())
+    }
+
+    /// Reads data into `buf` starting at `offset`, like `pread`.
+    ///
+    /// Keeps reading until `buf` is full or the end of the archive is
+    /// reached, and returns the number of bytes read. A count less than
+    /// `buf.len()` always means the end of the archive was reached; an
+    /// `offset` at or past the end returns `0`.
     ///
     /// # Errors
     ///
-    /// Returns an error if the range is invalid or if decompression fails.
+    /// Returns an error if decompression fails. Bytes already copied into
+    /// `buf` are left there.
     pub fn read_at(&mut self, buf: &mut [u8], offset: u64) -> Result<usize, Error> {
-        // Read range starting at offset with len = buf.len()
-        let end = offset + buf.len() as u64;
-        let data = self.read_range(offset, end)?;
-        let len = std::cmp::min(buf.len(), data.len());
-        buf[..len].copy_from_slice(&data[..len]);
-        Ok(len)
+        let size = self.size();
+        let mut filled = 0;
+        while filled < buf.len() {
+            let start = offset.saturating_add(filled as u64);
+            if start >= size {
+                break;
+            }
+            let remaining = &mut buf[filled..];
+            let end = start.saturating_add(remaining.len() as u64);
+            let data = self.read_range(start, end)?;
+            if data.is_empty() {
+                break;
+            }
+            let len = data.len().min(remaining.len());
+            remaining[..len].copy_from_slice(&data[..len]);
+            filled += len;
+        }
+        Ok(filled)
     }
 
     /// Reads a range of bytes from `start` to `end`.
     ///
+    /// An `end` past the end of the archive is clamped, so the result can be
+    /// shorter than `end - start`; [`read_range_partial`](Self::read_range_partial)
+    /// reports when that happened. An empty range (`start == end`) returns an
+    /// empty `Vec` anywhere up to and including the end of the archive.
+    ///
+    /// The frames decompressed by a read that is not frame-aligned (one
+    /// frame, or two adjacent ones) are kept until the next such read. A
+    /// following read that falls entirely within them is copied out without
+    /// resetting the decoder or decompressing again, which makes many small
+    /// sequential reads cheap.
+    ///
     /// # Errors
     ///
+    /// Returns `Error::OutOfBounds` if `start` is past the end of the
+    /// archive, or at the end of a non-empty archive for a non-empty range.
     /// Returns an error if `end < start`, or if decompression fails.
     pub fn read_range(&mut self, start: u64, end: u64) -> Result<Vec<u8>, Error> {
         if end < start {
@@ -66,104 +528,1806 @@ impl<R: Read + Seek> Decoder<'_, R> {
         let start_frame = self.inner.frame_index_decomp(start);
         let end_frame = self.inner.frame_index_decomp(end.saturating_sub(1)); // inclusive
 
-        // 2. Configure decoder
-        self.inner.set_lower_frame(start_frame);
-        self.inner.set_upper_frame(end_frame);
+        self.read_frames(start, end, start_frame, end_frame)
+    }
 
-        // 3. Decompress
-        let start_offset = self
-            .inner
-            .frame_start_decomp(start_frame)
-            .map_err(Error::from)?;
+    /// Reads a range like [`read_range`](Self::read_range), and reports
+    /// whether `end` was clamped to the end of the archive.
+    ///
+    /// `read_range` keeps returning a plain `Vec<u8>`; this variant saves
+    /// callers from comparing the length with `end - start` themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error [`read_range`](Self::read_range) would return.
+    pub fn read_range_partial(&mut self, start: u64, end: u64) -> Result<ReadRangeResult, Error> {
+        let data = self.read_range(start, end)?;
+        let truncated = (data.len() as u64) < end - start;
+        Ok(ReadRangeResult { data, truncated })
+    }
 
-        let skip = usize::try_from(start - start_offset)
-            .map_err(|_| Error::Format("Offset too large for usize".to_string()))?;
+    /// Reads a range like [`read_range`](Self::read_range), with `prefix`
+    /// placed in front of the data.
+    ///
+    /// For protocols that put a length header or content-type byte before
+    /// each payload. The result is allocated once at its final size, so
+    /// appending the data after the prefix never reallocates.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error [`read_range`](Self::read_range) would return.
+    pub fn read_range_prefixed(
+        &mut self,
+        start: u64,
+        end: u64,
+        prefix: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        if end < start {
+            return Err(Error::Format(
+                "End offset cannot be less than start offset".to_string(),
+            ));
+        }
+        self.check_in_bounds(start, end)?;
+        let len = end.min(self.size()).saturating_sub(start);
+        let len = usize::try_from(len).map_err(|_| Error::Format("Range too large".to_string()))?;
 
-        let len = usize::try_from(end - start)
-            .map_err(|_| Error::Format("Length too large for usize".to_string()))?;
+        let mut output = Vec::with_capacity(prefix.len() + len);
+        output.extend_from_slice(prefix);
+        self.read_range_into_writer(start, end, &mut output)?;
+        Ok(output)
+    }
 
-        // Calculate max size needed
-        let range_end_offset = self
-            .inner
-            .frame_end_decomp(end_frame)
-            .map_err(Error::from)?;
-        let total_decompressed_size = usize::try_from(range_end_offset - start_offset)
-            .map_err(|_| Error::Format("Decompressed size too large for usize".to_string()))?;
+    /// Reads several ranges, decompressing each distinct range only once.
+    ///
+    /// Results are returned in request order. Repeated `(start, end)` pairs
+    /// share one buffer, so fan-out reads of the same range are cheap.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error `read_range` reports for any range.
+    pub fn read_ranges(&mut self, ranges: &[(u64, u64)]) -> Result<Vec<Arc<Vec<u8>>>, Error> {
+        let mut unique: HashMap<(u64, u64), Arc<Vec<u8>>> = HashMap::new();
+        let mut results = Vec::with_capacity(ranges.len());
+        for &(start, end) in ranges {
+            let data = match unique.entry((start, end)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(Arc::new(self.read_range(start, end)?)),
+            };
+            results.push(Arc::clone(data));
+        }
+        Ok(results)
+    }
 
-        let mut temp_buf = vec![0u8; total_decompressed_size];
+    /// Reads a range and checksums every frame it touches.
+    ///
+    /// The covered frames are decompressed once; each is hashed in full and
+    /// the requested bytes are cut out of them. Callers can compare the
+    /// checksums against values stored elsewhere without a separate
+    /// verification pass. An empty range returns no checksums. Available
+    /// with the `xxhash` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error [`read_range`](Self::read_range) would return for
+    /// the same range.
+    #[cfg(feature = "xxhash")]
+    pub fn read_range_with_checksums(
+        &mut self,
+        start: u64,
+        end: u64,
+    ) -> Result<(Vec<u8>, Vec<FrameChecksum>), Error> {
+        let clamped_end = end.min(self.size());
+        if start >= clamped_end {
+            return Ok((self.read_range(start, end)?, Vec::new()));
+        }
 
-        // Reset decoder state
-        self.inner.reset();
+        let first = self.inner.frame_index_decomp(start);
+        let last = self.inner.frame_index_decomp(clamped_end - 1);
+        let frames_start = self.inner.frame_start_decomp(first).map_err(Error::from)?;
+        let frames_end = self.inner.frame_end_decomp(last).map_err(Error::from)?;
+        let frames = self.read_frames(frames_start, frames_end, first, last)?;
 
-        // Read loop
-        let mut pos = 0;
-        while pos < total_decompressed_size {
-            let n = self
-                .inner
-                .decompress(&mut temp_buf[pos..])
-                .map_err(Error::from)?;
-            if n == 0 {
-                break;
-            }
-            pos += n;
+        let offset = |pos: u64| {
+            usize::try_from(pos - frames_start)
+                .map_err(|_| Error::Format("Offset too large for usize".to_string()))
+        };
+        let mut checksums = Vec::new();
+        for index in first..=last {
+            let from = offset(self.inner.frame_start_decomp(index).map_err(Error::from)?)?;
+            let to = offset(self.inner.frame_end_decomp(index).map_err(Error::from)?)?;
+            checksums.push(FrameChecksum {
+                frame_index: u64::from(index),
+                xxhash: xxhash_rust::xxh64::xxh64(&frames[from..to], 0),
+            });
         }
 
-        // Extract the requested range
-        let available = pos;
-        if skip >= available {
-            return Ok(Vec::new());
-        }
+        let data = frames[offset(start)?..offset(clamped_end)?].to_vec();
+        self.position = clamped_end;
+        Ok((data, checksums))
+    }
 
-        let end_idx = std::cmp::min(skip + len, available);
-        Ok(temp_buf[skip..end_idx].to_vec())
+    /// Returns a handle that reads `start..end` only when first accessed.
+    ///
+    /// The frames covering the range are looked up now; decompression waits
+    /// until [`LazyDecompressedRange::as_slice`] is called. Useful for
+    /// speculative reads that may turn out not to be needed.
+    pub fn read_range_lazy(&mut self, start: u64, end: u64) -> LazyDecompressedRange<'_, 'a, R> {
+        let frames = (end > start && start < self.size()).then(|| {
+            (
+                self.inner.frame_index_decomp(start),
+                self.inner.frame_index_decomp(end - 1),
+            )
+        });
+        LazyDecompressedRange {
+            decoder: self,
+            start,
+            end,
+            frames,
+            data: None,
+        }
     }
-}
 
-impl Decoder<'_, File> {
-    /// Opens a seekable zstd archive from a file path.
+    /// Reads a range of bytes using caller-supplied frame indices.
     ///
-    /// This is a convenience method equivalent to:
-    /// ```ignore
-    /// let file = File::open(path)?;
-    /// Decoder::new(file)
-    /// ```
+    /// `start_frame` and `end_frame` (inclusive) must be the frames containing
+    /// `start` and `end - 1`, as returned by a previous seek table lookup. This
+    /// skips the lookup in `read_range`. Debug builds check the hints and
+    /// panic on mismatch; release builds trust them, so hints that are wrong
+    /// but still cover `start` can return data from the wrong frames.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be opened or if the decoder
-    /// cannot be initialized.
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
-        let file = File::open(path)?;
-        Self::new(file)
-    }
-}
+    /// Returns an error if `end < start`, if `start` is at or beyond the end
+    /// of a non-empty archive, if a frame index is out of range, or if
+    /// decompression fails.
+    pub fn read_range_with_hints(
+        &mut self,
+        start: u64,
+        end: u64,
+        start_frame: u64,
+        end_frame: u64,
+    ) -> Result<Vec<u8>, Error> {
+        if end < start {
+            return Err(Error::Format(
+                "End offset cannot be less than start offset".to_string(),
+            ));
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::encoder::Encoder;
-    use std::io::Cursor;
+        let start_frame = u32::try_from(start_frame)
+            .map_err(|_| Error::Format("Start frame index too large".to_string()))?;
+        let end_frame = u32::try_from(end_frame)
+            .map_err(|_| Error::Format("End frame index too large".to_string()))?;
 
-    #[test]
-    fn test_roundtrip() {
-        // Compress
-        let mut buffer = Vec::new();
-        let mut encoder = Encoder::new(&mut buffer).unwrap();
-        let data = b"Hello World, this is a test of seekable zstd.";
-        encoder.write_all(data).unwrap();
-        encoder.finish().unwrap();
+        debug_assert_eq!(
+            start_frame,
+            self.inner.frame_index_decomp(start),
+            "start_frame hint does not match the seek table"
+        );
+        debug_assert_eq!(
+            end_frame,
+            self.inner.frame_index_decomp(end.saturating_sub(1)),
+            "end_frame hint does not match the seek table"
+        );
 
-        // Decompress
-        let cursor = Cursor::new(buffer);
-        let mut decoder = Decoder::new(cursor).unwrap();
+        if start_frame > end_frame {
+            return Err(Error::Format(
+                "End frame cannot be less than start frame".to_string(),
+            ));
+        }
 
-        assert_eq!(decoder.size(), data.len() as u64);
+        self.read_frames(start, end, start_frame, end_frame)
+    }
 
-        let read_data = decoder.read_range(0, data.len() as u64).unwrap();
-        assert_eq!(read_data, data);
+    /// Decompresses `start..end` into `writer` without buffering the whole range.
+    ///
+    /// Data is decompressed and written in chunks of at most one frame, so
+    /// memory use stays bounded by the largest frame regardless of the range
+    /// length. Returns the number of bytes written, which is less than
+    /// `end - start` only if `end` is past the end of the archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `end < start`, if decompression fails, or if
+    /// writing to `writer` fails. Data written before the error is not rolled
+    /// back.
+    pub fn read_range_into_writer<W: Write>(
+        &mut self,
+        start: u64,
+        end: u64,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        self.for_each_chunk(start, end, |chunk| {
+            writer.write_all(chunk)?;
+            Ok(())
+        })
+    }
 
-        // Random access
-        let partial = decoder.read_range(6, 11).unwrap();
-        assert_eq!(partial, b"World");
+    /// Decompresses `start..end` into `output` starting at `output_offset`.
+    ///
+    /// Writes with positioned writes (`pwrite`), so the file's cursor is
+    /// neither used nor moved. Several ranges can be decoded into separate
+    /// regions of one output file, from several decoders at once, without
+    /// seeking. Memory use is bounded by the largest frame, as with
+    /// [`read_range_into_writer`](Self::read_range_into_writer). Returns the
+    /// number of bytes written. Unix only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `end < start`, if decompression fails, or if
+    /// writing to `output` fails. Data written before the error is not
+    /// rolled back.
+    #[cfg(unix)]
+    pub fn read_range_into_file(
+        &mut self,
+        start: u64,
+        end: u64,
+        output: &File,
+        output_offset: u64,
+    ) -> Result<u64, Error> {
+        use std::os::unix::fs::FileExt;
+
+        let mut offset = output_offset;
+        self.for_each_chunk(start, end, |chunk| {
+            output.write_all_at(chunk, offset)?;
+            offset += chunk.len() as u64;
+            Ok(())
+        })
+    }
+
+    /// Decompresses `start..end` and sends it to `sender` in chunks of at
+    /// most one frame.
+    ///
+    /// The consumer can start on the first chunk while later frames are
+    /// still being decompressed. This call blocks: run it on a blocking
+    /// thread, for example inside `tokio::task::spawn_blocking`, not
+    /// directly on the async runtime. Waits for channel capacity, so a slow
+    /// consumer throttles decompression.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `end < start`, if decompression fails, or with
+    /// `io::ErrorKind::BrokenPipe` if the receiver is dropped before the
+    /// range is complete.
+    #[cfg(feature = "tokio")]
+    // Taking the sender by value drops it on return, which closes the channel
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn read_range_into_channel(
+        &mut self,
+        start: u64,
+        end: u64,
+        sender: tokio::sync::mpsc::Sender<Vec<u8>>,
+    ) -> Result<(), Error> {
+        self.for_each_chunk(start, end, |chunk| {
+            sender.blocking_send(chunk.to_vec()).map_err(|_| {
+                Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "Channel receiver was dropped",
+                ))
+            })
+        })?;
+        Ok(())
+    }
+
+    /// Decompresses `start..end` and passes it to `f` in chunks of at most
+    /// one frame. Returns the number of bytes passed.
+    fn for_each_chunk(
+        &mut self,
+        start: u64,
+        end: u64,
+        mut f: impl FnMut(&[u8]) -> Result<(), Error>,
+    ) -> Result<u64, Error> {
+        if end < start {
+            return Err(Error::Format(
+                "End offset cannot be less than start offset".to_string(),
+            ));
+        }
+        self.check_in_bounds(start, end)?;
+        if start == end || start >= self.size() {
+            self.position = start;
+            return Ok(0);
+        }
+
+        let start_frame = self.inner.frame_index_decomp(start);
+        let end_frame = self.inner.frame_index_decomp(end.saturating_sub(1));
+        self.inner.set_lower_frame(start_frame);
+        self.inner.set_upper_frame(end_frame);
+        self.inner.reset();
+
+        let chunk_size = usize::try_from(self.inner.max_frame_size_decomp())
+            .map_err(|_| Error::Format("Frame size too large for usize".to_string()))?;
+        let mut chunk = vec![0u8; chunk_size.max(1)];
+        let mut chunk_start = self
+            .inner
+            .frame_start_decomp(start_frame)
+            .map_err(Error::from)?;
+        let mut written = 0u64;
+
+        while chunk_start < end {
+            let n = self.inner.decompress(&mut chunk).map_err(Error::from)?;
+            if n == 0 {
+                break;
+            }
+            let chunk_end = chunk_start + n as u64;
+            // Both bounds lie within the n bytes just decompressed
+            #[allow(clippy::cast_possible_truncation)]
+            let from = start.saturating_sub(chunk_start).min(n as u64) as usize;
+            #[allow(clippy::cast_possible_truncation)]
+            let to = (end.min(chunk_end) - chunk_start) as usize;
+            if from < to {
+                f(&chunk[from..to])?;
+                written += (to - from) as u64;
+            }
+            chunk_start = chunk_end;
+        }
+
+        self.position = start + written;
+        Ok(written)
+    }
+
+    /// Reads the block stored in frame `block_index`.
+    ///
+    /// Intended for archives written with [`Encoder::compress_blocks`], where
+    /// each block occupies exactly one frame. Any data written before the
+    /// blocks occupies the leading frames, so the first block's index is
+    /// offset accordingly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `block_index` is out of range, or if
+    /// decompression fails.
+    ///
+    /// [`Encoder::compress_blocks`]: crate::Encoder::compress_blocks
+    pub fn read_block(&mut self, block_index: u64) -> Result<Vec<u8>, Error> {
+        self.read_frame(block_index)
+    }
+
+    /// Reads the full decompressed contents of frame `frame_index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `frame_index` is out of range, or if
+    /// decompression fails.
+    pub fn read_frame(&mut self, frame_index: u64) -> Result<Vec<u8>, Error> {
+        let index = u32::try_from(frame_index)
+            .ok()
+            .filter(|index| *index < self.inner.num_frames())
+            .ok_or_else(|| Error::Format(format!("Frame {frame_index} out of range")))?;
+        self.read_whole_frame(index)
+    }
+
+    /// Reads a frame counted from the end of the archive.
+    ///
+    /// `from_end = 0` is the last frame, `1` the second to last, and so on.
+    /// Useful for tailing logs, where the newest data is wanted first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from_end` is not less than the frame count, or
+    /// if decompression fails.
+    pub fn read_frame_rev(&mut self, from_end: u64) -> Result<Vec<u8>, Error> {
+        let index = self
+            .frame_count()
+            .checked_sub(from_end)
+            .and_then(|n| n.checked_sub(1))
+            .ok_or_else(|| Error::Format(format!("Frame {from_end} from the end out of range")))?;
+        self.read_frame(index)
+    }
+
+    /// Returns an iterator over frames from last to first.
+    ///
+    /// Each item is the decompressed contents of one frame, as returned by
+    /// [`read_frame`](Self::read_frame).
+    pub fn frame_iter_rev(&mut self) -> RevFrameIter<'_, 'a, R> {
+        let remaining = self.frame_count();
+        RevFrameIter {
+            decoder: self,
+            remaining,
+        }
+    }
+
+    /// Turns the decoder into a `Read + Seek` stream over the decompressed
+    /// data.
+    ///
+    /// The stream starts at the current position and decompresses frames
+    /// only as reads reach them, so it can be handed to anything that
+    /// consumes a reader (`serde_json::from_reader`, `csv::Reader`, image
+    /// decoders) without materializing the archive.
+    #[must_use]
+    pub fn into_stream(self) -> DecoderStream<'a, R> {
+        DecoderStream {
+            position: self.position,
+            decoder: self,
+        }
+    }
+
+    /// Reads frame `frame_index` and parses it as a JSON document.
+    ///
+    /// For archives that store one UTF-8 JSON value per frame. Available
+    /// with the `serde_json` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Format` if the frame is not valid JSON, or any error
+    /// [`read_frame`](Self::read_frame) would return.
+    #[cfg(feature = "serde_json")]
+    pub fn decode_frame_as_json(&mut self, frame_index: u64) -> Result<serde_json::Value, Error> {
+        let frame = self.read_frame(frame_index)?;
+        serde_json::from_slice(&frame)
+            .map_err(|e| Error::Format(format!("Frame {frame_index} is not valid JSON: {e}")))
+    }
+
+    /// Parses every frame that overlaps `start..end` as a JSON document.
+    ///
+    /// Frames are parsed whole, even when the range starts or ends inside
+    /// one, since a partial frame is not a complete document. An empty range
+    /// returns no values. Available with the `serde_json` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::OutOfBounds` if `start` is past the end of the
+    /// archive, `Error::Format` if a frame is not valid JSON, or an error if
+    /// decompression fails.
+    #[cfg(feature = "serde_json")]
+    pub fn decode_range_as_json_array(
+        &mut self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<serde_json::Value>, Error> {
+        self.check_in_bounds(start, end)?;
+        let end = end.min(self.size());
+        if start >= end {
+            return Ok(Vec::new());
+        }
+        let first = self.inner.frame_index_decomp(start);
+        let last = self.inner.frame_index_decomp(end - 1);
+        (first..=last)
+            .map(|index| self.decode_frame_as_json(u64::from(index)))
+            .collect()
+    }
+
+    fn read_whole_frame(&mut self, index: u32) -> Result<Vec<u8>, Error> {
+        let start = self.inner.frame_start_decomp(index).map_err(Error::from)?;
+        let end = self.inner.frame_end_decomp(index).map_err(Error::from)?;
+        self.read_frames(start, end, index, index)
+    }
+
+    /// Hashes the full decompressed contents of the archive.
+    ///
+    /// Data is streamed through the hasher one frame at a time, so memory use
+    /// does not grow with the archive size. This is independent of the
+    /// optional per-frame zstd checksums. Available when at least one of the
+    /// `sha2`, `blake3`, or `xxhash` features is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decompression fails.
+    #[cfg(any(feature = "sha2", feature = "blake3", feature = "xxhash"))]
+    pub fn content_hash(&mut self, algorithm: HashAlgorithm) -> Result<Vec<u8>, Error> {
+        let mut hasher = Hasher::new(algorithm);
+        let size = self.size();
+        self.read_range_into_writer(0, size, &mut hasher)?;
+        Ok(hasher.finalize())
+    }
+
+    /// Reads and decompresses every frame once, discarding the output.
+    ///
+    /// This pulls the whole archive through the OS page cache so that later
+    /// random-access reads do not pay for cold storage. It blocks until the
+    /// last frame has been read. Each frame is traced in a `warm_frame` debug
+    /// span. The current position is not changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading or decompressing any frame fails.
+    pub fn block_until_warm(&mut self) -> Result<(), Error> {
+        let num_frames = self.inner.num_frames();
+        let chunk_size = usize::try_from(self.inner.max_frame_size_decomp())
+            .map_err(|_| Error::Format("Frame size too large for usize".to_string()))?;
+        let mut scratch = vec![0u8; chunk_size.max(1)];
+
+        for index in 0..num_frames {
+            let _span = tracing::debug_span!("warm_frame", index, num_frames).entered();
+            self.inner.set_lower_frame(index);
+            self.inner.set_upper_frame(index);
+            while self.inner.decompress(&mut scratch).map_err(Error::from)? > 0 {}
+            tracing::debug!(
+                compressed_bytes = self.inner.read_compressed(),
+                "frame warmed"
+            );
+        }
+        Ok(())
+    }
+
+    /// Decompresses the whole archive and returns its contents.
+    ///
+    /// Intended for small archives; the full decompressed size is allocated
+    /// at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decompression fails.
+    pub fn into_vec(mut self) -> Result<Vec<u8>, Error> {
+        let size = self.size();
+        self.read_range(0, size)
+    }
+
+    /// Decompresses the whole archive and returns it as a UTF-8 string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decompression fails, or `Error::Format` if the
+    /// contents are not valid UTF-8.
+    pub fn into_string(self) -> Result<String, Error> {
+        String::from_utf8(self.into_vec()?)
+            .map_err(|e| Error::Format(format!("Archive is not valid UTF-8: {e}")))
+    }
+
+    /// Reads the whole first frame.
+    ///
+    /// Frame 0 always starts at offset 0 in both the compressed and the
+    /// decompressed stream, so this skips the offset-to-frame search that
+    /// `read_range` does. The seek table is already in memory once the
+    /// decoder is open, so the saving is the lookup, not any I/O. Returns an
+    /// empty vector for an archive without frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decompression fails.
+    pub fn read_first_frame(&mut self) -> Result<Vec<u8>, Error> {
+        if self.inner.num_frames() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let len = usize::try_from(self.inner.frame_size_decomp(0).map_err(Error::from)?)
+            .map_err(|_| Error::Format("Frame size too large for usize".to_string()))?;
+        let mut data = vec![0u8; len];
+
+        self.inner.set_lower_frame(0);
+        self.inner.set_upper_frame(0);
+        let mut pos = 0;
+        while pos < len {
+            let n = self
+                .inner
+                .decompress(&mut data[pos..])
+                .map_err(Error::from)?;
+            if n == 0 {
+                break;
+            }
+            pos += n;
+        }
+        data.truncate(pos);
+
+        self.position = pos as u64;
+        Ok(data)
+    }
+
+    /// Reads whole frames covering `approx_start..approx_end`.
+    ///
+    /// `approx_start` is rounded down to the start of its frame and
+    /// `approx_end` up to the end of its frame (clamped to the archive size).
+    /// Returns the data together with the `(actual_start, actual_end)` range
+    /// it covers, so the caller can slice out the bytes it needs. An empty
+    /// range still returns the frame containing `approx_start`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `approx_end < approx_start`, if `approx_start` is
+    /// at or beyond the end of a non-empty archive, or if decompression
+    /// fails.
+    pub fn read_at_frame_aligned(
+        &mut self,
+        approx_start: u64,
+        approx_end: u64,
+    ) -> Result<(Vec<u8>, (u64, u64)), Error> {
+        if approx_end < approx_start {
+            return Err(Error::Format(
+                "End offset cannot be less than start offset".to_string(),
+            ));
+        }
+        let size = self.size();
+        if size == 0 {
+            return Ok((Vec::new(), (0, 0)));
+        }
+        if approx_start >= size {
+            return Err(Error::OutOfBounds {
+                offset: approx_start,
+                size,
+            });
+        }
+
+        let start_frame = self.inner.frame_index_decomp(approx_start);
+        let end_frame = self
+            .inner
+            .frame_index_decomp(approx_end.max(approx_start + 1) - 1);
+        let start = self
+            .inner
+            .frame_start_decomp(start_frame)
+            .map_err(Error::from)?;
+        let end = self
+            .inner
+            .frame_end_decomp(end_frame)
+            .map_err(Error::from)?;
+
+        let data = self.read_frames(start, end, start_frame, end_frame)?;
+        Ok((data, (start, end)))
+    }
+
+    fn check_in_bounds(&self, start: u64, end: u64) -> Result<(), Error> {
+        check_in_bounds(start, end, self.size())
+    }
+
+    /// Decompresses frames `start_frame..=end_frame` and returns `start..end`.
+    fn read_frames(
+        &mut self,
+        start: u64,
+        end: u64,
+        start_frame: u32,
+        end_frame: u32,
+    ) -> Result<Vec<u8>, Error> {
+        self.check_in_bounds(start, end)?;
+        if start == end {
+            self.position = start;
+            return Ok(Vec::new());
+        }
+
+        if let Some(frames) = &self.last_frames {
+            if frames.first_frame <= start_frame && end_frame <= frames.last_frame {
+                // Offsets fall inside `frames.data`, which fits in memory
+                let skip = usize::try_from(start - frames.start).unwrap_or(usize::MAX);
+                let end_idx = usize::try_from(end - frames.start)
+                    .unwrap_or(usize::MAX)
+                    .min(frames.data.len());
+                let data = frames.data.get(skip..end_idx).unwrap_or_default().to_vec();
+                self.position = start + data.len() as u64;
+                self.stats.reads += 1;
+                self.stats.frame_cache_hits += 1;
+                return Ok(data);
+            }
+        }
+
+        // 2. Configure decoder
+        self.inner.set_lower_frame(start_frame);
+        self.inner.set_upper_frame(end_frame);
+
+        // 3. Decompress
+        let start_offset = self
+            .inner
+            .frame_start_decomp(start_frame)
+            .map_err(Error::from)?;
+
+        let skip = start
+            .checked_sub(start_offset)
+            .ok_or_else(|| Error::Format("Start offset precedes start frame".to_string()))?;
+        let skip = usize::try_from(skip)
+            .map_err(|_| Error::Format("Offset too large for usize".to_string()))?;
+
+        let len = usize::try_from(end - start)
+            .map_err(|_| Error::Format("Length too large for usize".to_string()))?;
+
+        // Calculate max size needed
+        let range_end_offset = self
+            .inner
+            .frame_end_decomp(end_frame)
+            .map_err(Error::from)?;
+        let total_decompressed_size = usize::try_from(range_end_offset - start_offset)
+            .map_err(|_| Error::Format("Decompressed size too large for usize".to_string()))?;
+
+        let mut temp_buf = vec![0u8; total_decompressed_size];
+
+        // Reset decoder state
+        self.inner.reset();
+        let started = Instant::now();
+
+        // Read loop
+        let mut pos = 0;
+        while pos < total_decompressed_size {
+            let n = self
+                .inner
+                .decompress(&mut temp_buf[pos..])
+                .map_err(Error::from)?;
+            if n == 0 {
+                break;
+            }
+            pos += n;
+        }
+        self.stats.record_speed(pos, started.elapsed());
+
+        // Extract the requested range
+        let available = pos;
+        if skip >= available {
+            self.position = start;
+            return Ok(Vec::new());
+        }
+
+        let end_idx = std::cmp::min(skip + len, available);
+        self.position = start + (end_idx - skip) as u64;
+        self.stats.reads += 1;
+
+        // Exact frame reads return the buffer as is, without copying
+        if skip == 0 && end_idx == total_decompressed_size {
+            self.stats.exact_frame_reads += 1;
+            temp_buf.truncate(end_idx);
+            return Ok(temp_buf);
+        }
+        let data = temp_buf[skip..end_idx].to_vec();
+        // Keep at most two frames, enough for reads that straddle a boundary
+        if end_frame.saturating_sub(start_frame) <= 1 {
+            temp_buf.truncate(available);
+            self.last_frames = Some(DecodedFrames {
+                first_frame: start_frame,
+                last_frame: end_frame,
+                start: start_offset,
+                data: temp_buf,
+            });
+        }
+        Ok(data)
+    }
+}
+
+/// A range read that is decompressed on first access.
+///
+/// Created by [`Decoder::read_range_lazy`]. The decompressed data is cached
+/// after the first successful [`as_slice`](Self::as_slice) call and freed
+/// when the handle is dropped.
+pub struct LazyDecompressedRange<'d, 'a, R: Read + Seek> {
+    decoder: &'d mut Decoder<'a, R>,
+    start: u64,
+    end: u64,
+    /// First and last frame of the range, or `None` if the range is empty or
+    /// out of bounds, in which case `read_range` reports the outcome.
+    frames: Option<(u32, u32)>,
+    data: Option<Vec<u8>>,
+}
+
+impl<R: Read + Seek> LazyDecompressedRange<'_, '_, R> {
+    /// Returns `true` once the range has been decompressed.
+    #[must_use]
+    pub fn is_loaded(&self) -> bool {
+        self.data.is_some()
+    }
+
+    /// Decompresses the range on the first call and returns the cached data
+    /// on later calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error `read_range` would return for the same range. A
+    /// failed call is not cached, so it can be retried.
+    pub fn as_slice(&mut self) -> Result<&[u8], Error> {
+        let data = match self.data.take() {
+            Some(data) => data,
+            None => match self.frames {
+                Some((start_frame, end_frame)) => {
+                    self.decoder
+                        .read_frames(self.start, self.end, start_frame, end_frame)?
+                }
+                None => self.decoder.read_range(self.start, self.end)?,
+            },
+        };
+        Ok(self.data.insert(data))
+    }
+}
+
+/// Iterator over a decoder's frames from last to first.
+///
+/// Created by [`Decoder::frame_iter_rev`].
+pub struct RevFrameIter<'d, 'a, R: Read + Seek> {
+    decoder: &'d mut Decoder<'a, R>,
+    remaining: u64,
+}
+
+impl<R: Read + Seek> Iterator for RevFrameIter<'_, '_, R> {
+    type Item = Result<Vec<u8>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        Some(self.decoder.read_frame(self.remaining))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = usize::try_from(self.remaining).unwrap_or(usize::MAX);
+        (remaining, Some(remaining))
+    }
+}
+
+/// A [`Decoder`] used as a `Read + Seek` stream of decompressed bytes.
+///
+/// Created by [`Decoder::into_stream`]. Each read returns data from at most
+/// one frame; the frame is decompressed on the first read that reaches it
+/// and reused by following reads within it. Seeking only moves the cursor,
+/// and seeking past the end is allowed, with reads there returning `0`.
+pub struct DecoderStream<'a, R: Read + Seek> {
+    decoder: Decoder<'a, R>,
+    position: u64,
+}
+
+impl<'a, R: Read + Seek> DecoderStream<'a, R> {
+    /// Returns the underlying decoder, positioned where the stream was.
+    #[must_use]
+    pub fn into_inner(self) -> Decoder<'a, R> {
+        let mut decoder = self.decoder;
+        decoder.position = self.position.min(decoder.size());
+        decoder
+    }
+}
+
+impl<R: Read + Seek> Read for DecoderStream<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() || self.position >= self.decoder.size() {
+            return Ok(0);
+        }
+        let inner = &self.decoder.inner;
+        let frame = inner.frame_index_decomp(self.position);
+        let frame_end = inner.frame_end_decomp(frame).map_err(Error::from)?;
+        let end = frame_end.min(self.position.saturating_add(buf.len() as u64));
+
+        let data = self.decoder.read_range(self.position, end)?;
+        buf[..data.len()].copy_from_slice(&data);
+        self.position += data.len() as u64;
+        Ok(data.len())
+    }
+}
+
+impl<R: Read + Seek> Seek for DecoderStream<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => {
+                self.position = offset;
+                return Ok(offset);
+            }
+            SeekFrom::End(offset) => (self.decoder.size(), offset),
+            SeekFrom::Current(offset) => (self.position, offset),
+        };
+        self.position = base.checked_add_signed(offset).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+impl Decoder<'_, File> {
+    /// Opens a seekable zstd archive from a file path.
+    ///
+    /// This is a convenience method equivalent to:
+    /// ```ignore
+    /// let file = File::open(path)?;
+    /// Decoder::new(file)
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or if the decoder
+    /// cannot be initialized.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::open(path)?;
+        Self::new(file)
+    }
+
+    /// Opens an archive and takes a shared lock on it.
+    ///
+    /// The lock is acquired before the seek table is read and held until
+    /// the returned [`FileLock`](crate::FileLock) is dropped. While it is
+    /// held, [`Encoder::open_with_exclusive_lock`](crate::Encoder::open_with_exclusive_lock)
+    /// waits, so an append cannot rewrite the seek table mid-read. Blocks
+    /// while another process holds an exclusive lock. Unix only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or locked, or if the
+    /// decoder cannot be initialized.
+    #[cfg(unix)]
+    pub fn open_with_lock<P: AsRef<Path>>(path: P) -> Result<(Self, crate::FileLock), Error> {
+        let file = File::open(path)?;
+        let lock = crate::FileLock::shared(&file)?;
+        Ok((Self::new(file)?, lock))
+    }
+}
+
+/// Returns `Error::OutOfBounds` unless `start..end` begins inside an archive
+/// of `size` decompressed bytes. An empty range may sit at the very end.
+pub(crate) fn check_in_bounds(start: u64, end: u64, size: u64) -> Result<(), Error> {
+    if start > size || (size > 0 && start == size && end > start) {
+        return Err(Error::OutOfBounds {
+            offset: start,
+            size,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::Encoder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_roundtrip() {
+        // Compress
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::new(&mut buffer).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        // Decompress
+        let cursor = Cursor::new(buffer);
+        let mut decoder = Decoder::new(cursor).unwrap();
+
+        assert_eq!(decoder.size(), data.len() as u64);
+
+        let read_data = decoder.read_range(0, data.len() as u64).unwrap();
+        assert_eq!(read_data, data);
+
+        // Random access
+        let partial = decoder.read_range(6, 11).unwrap();
+        assert_eq!(partial, b"World");
+    }
+
+    #[test]
+    fn test_frame_histogram() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 16).unwrap();
+        // Two full 16-byte frames and a trailing 4-byte frame
+        encoder.write_all(&[7u8; 36]).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.frame_histogram(10), vec![1, 2]);
+        assert_eq!(decoder.frame_histogram(64), vec![3]);
+        assert!(decoder.frame_histogram(0).is_empty());
+    }
+
+    #[test]
+    fn test_read_range_with_hints() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        // Bytes 6..20 span frames 0 (0..8) through 2 (16..24)
+        let read = decoder.read_range_with_hints(6, 20, 0, 2).unwrap();
+        assert_eq!(read, &data[6..20]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "start_frame hint")]
+    fn test_read_range_with_wrong_hints() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        encoder.write_all(&[1u8; 32]).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let _ = decoder.read_range_with_hints(10, 12, 0, 1);
+    }
+
+    #[test]
+    fn test_new_over_device_like_reader() {
+        /// A raw device: no `metadata`, and the archive is followed by unused
+        /// space, with `SeekFrom::End` reporting where the archive ends.
+        struct Device {
+            inner: Cursor<Vec<u8>>,
+            end: u64,
+        }
+
+        impl Read for Device {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let left = self.end.saturating_sub(self.inner.position());
+                let len = buf.len().min(usize::try_from(left).unwrap_or(usize::MAX));
+                self.inner.read(&mut buf[..len])
+            }
+        }
+
+        impl Seek for Device {
+            fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+                match pos {
+                    SeekFrom::End(offset) => self
+                        .inner
+                        .seek(SeekFrom::Start(self.end.saturating_add_signed(offset))),
+                    pos => self.inner.seek(pos),
+                }
+            }
+        }
+
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 64).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+        let end = buffer.len() as u64;
+        buffer.resize(buffer.len() + 4096, 0);
+
+        let mut decoder = Decoder::new(Device {
+            inner: Cursor::new(buffer),
+            end,
+        })
+        .unwrap();
+        assert_eq!(decoder.size(), 1000);
+        assert_eq!(decoder.read_range(100, 900).unwrap(), &data[100..900]);
+    }
+
+    #[test]
+    fn test_from_reader_and_seek_table() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let seek_table = Decoder::new(Cursor::new(&buffer)).unwrap().seek_table();
+        let mut decoder =
+            Decoder::from_reader_and_seek_table(Cursor::new(&buffer), &seek_table).unwrap();
+        assert_eq!(decoder.frame_count(), 6);
+        assert_eq!(decoder.read_range(6, 20).unwrap(), &data[6..20]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_from_reader_and_mismatched_seek_table() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        encoder.write_all(&[1u8; 32]).unwrap();
+        encoder.finish().unwrap();
+        let mut other = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut other, 16).unwrap();
+        encoder.write_all(&[1u8; 32]).unwrap();
+        encoder.finish().unwrap();
+
+        let seek_table = Decoder::new(Cursor::new(&other)).unwrap().seek_table();
+        assert!(matches!(
+            Decoder::from_reader_and_seek_table(Cursor::new(&buffer), &seek_table),
+            Err(Error::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_into_vec_and_string() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = "Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = Decoder::new(Cursor::new(&buffer)).unwrap();
+        assert_eq!(decoder.into_vec().unwrap(), data.as_bytes());
+        let decoder = Decoder::new(Cursor::new(&buffer)).unwrap();
+        assert_eq!(decoder.into_string().unwrap(), data);
+
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::new(&mut buffer).unwrap();
+        encoder.write_all(&[0xff, 0xfe]).unwrap();
+        encoder.finish().unwrap();
+        let decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert!(matches!(decoder.into_string(), Err(Error::Format(_))));
+    }
+
+    #[test]
+    fn test_new_verified() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new_verified(Cursor::new(buffer.clone())).unwrap();
+        assert_eq!(decoder.read_range(0, 5).unwrap(), b"Hello");
+
+        // Corrupt the magic number of the first frame
+        let mut corrupted = buffer.clone();
+        corrupted[0] ^= 0xff;
+        assert!(Decoder::new(Cursor::new(corrupted.clone())).is_ok());
+        assert!(matches!(
+            Decoder::new_verified(Cursor::new(corrupted)),
+            Err(Error::Format(_))
+        ));
+
+        // A reserved frame header bit, or a legacy magic number, is reported
+        // as an unsupported feature
+        let mut reserved = buffer.clone();
+        reserved[4] |= 0x08;
+        let Err(Error::Format(msg)) = Decoder::new_verified(Cursor::new(reserved)) else {
+            panic!("expected a format error");
+        };
+        assert!(msg.starts_with("frame 0 uses unsupported zstd feature: 0x0008"));
+        let mut legacy = buffer.clone();
+        legacy[0] = 0x27;
+        let Err(Error::Format(msg)) = Decoder::new_verified(Cursor::new(legacy)) else {
+            panic!("expected a format error");
+        };
+        assert!(msg.contains("0xFD2FB527"));
+
+        // Prepend garbage so the frames no longer start at offset 0
+        let mut shifted = vec![0u8; 3];
+        shifted.extend_from_slice(&buffer);
+        assert!(matches!(
+            Decoder::new_verified(Cursor::new(shifted)),
+            Err(Error::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_range_into_writer() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 64).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        for (start, end) in [(0, 1000), (10, 20), (60, 70), (100, 900), (990, 2000)] {
+            let mut out = Vec::new();
+            let written = decoder
+                .read_range_into_writer(start as u64, end as u64, &mut out)
+                .unwrap();
+            let expected = &data[start..end.min(1000)];
+            assert_eq!(out, expected);
+            assert_eq!(written, expected.len() as u64);
+        }
+        assert!(matches!(
+            decoder.read_range_into_writer(1001, 2000, &mut Vec::new()),
+            Err(Error::OutOfBounds {
+                offset: 1001,
+                size: 1000
+            })
+        ));
+        assert!(matches!(
+            decoder.read_range_into_writer(1000, 1001, &mut Vec::new()),
+            Err(Error::OutOfBounds { .. })
+        ));
+        assert_eq!(
+            decoder
+                .read_range_into_writer(1000, 1000, &mut Vec::new())
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_block_until_warm() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 64).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        decoder.seek_to(100).unwrap();
+        decoder.block_until_warm().unwrap();
+        assert_eq!(decoder.current_position(), 100);
+        assert_eq!(decoder.read_range(60, 70).unwrap(), &data[60..70]);
+    }
+
+    #[test]
+    fn test_read_range_out_of_bounds() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let size = data.len() as u64;
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert!(matches!(
+            decoder.read_range(size + 1, size + 100),
+            Err(Error::OutOfBounds { offset, size: 45 }) if offset == size + 1
+        ));
+        assert!(matches!(
+            decoder.read_range(size, size + 1),
+            Err(Error::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            decoder.read_range(size + 1, size + 1),
+            Err(Error::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            decoder.seek_to(size + 1),
+            Err(Error::OutOfBounds { .. })
+        ));
+
+        // An end past the archive is clamped, and read_range_partial says so
+        assert_eq!(decoder.read_range(0, size + 1).unwrap(), data);
+        assert!(decoder
+            .read_range_partial(40, size + 1)
+            .unwrap()
+            .is_truncated());
+
+        // Zero-length ranges, including at the very end
+        assert!(decoder.read_range(5, 5).unwrap().is_empty());
+        assert!(decoder.read_range(8, 8).unwrap().is_empty());
+        assert!(decoder.read_range(size, size).unwrap().is_empty());
+        assert_eq!(decoder.current_position(), size);
+    }
+
+    #[test]
+    fn test_read_at_frame_aligned() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let (aligned, (start, end)) = decoder.read_at_frame_aligned(10, 20).unwrap();
+        assert_eq!((start, end), (8, 24));
+        assert_eq!(aligned, &data[8..24]);
+
+        let (aligned, (start, end)) = decoder.read_at_frame_aligned(44, 100).unwrap();
+        assert_eq!((start, end), (40, 45));
+        assert_eq!(aligned, &data[40..]);
+
+        let (_, range) = decoder.read_at_frame_aligned(3, 3).unwrap();
+        assert_eq!(range, (0, 8));
+    }
+
+    #[test]
+    fn test_read_first_frame() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        decoder.read_range(20, 30).unwrap();
+        assert_eq!(decoder.read_first_frame().unwrap(), b"Hello Wo");
+        assert_eq!(decoder.current_position(), 8);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 16).unwrap();
+        encoder.write_all(&[7u8; 40]).unwrap();
+        encoder.finish().unwrap();
+        let archive_len = buffer.len() as u64;
+
+        let decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let metadata = decoder.metadata().unwrap();
+        assert_eq!(metadata.size, 40);
+        assert_eq!(metadata.frame_count, 3);
+        assert_eq!(metadata.max_frame_size, 16);
+        assert_eq!(
+            metadata.compressed_size + metadata.seek_table_size,
+            archive_len
+        );
+        assert!(metadata.compression_ratio > 0.0);
+        assert!(metadata.to_string().starts_with("40 bytes in 3 frames"));
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn test_content_hash() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        encoder.write_all(b"abc").unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let digest = decoder.content_hash(HashAlgorithm::Sha256).unwrap();
+        // SHA-256("abc") from FIPS 180-2
+        assert_eq!(
+            digest,
+            [
+                0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+                0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+                0xf2, 0x00, 0x15, 0xad,
+            ]
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_range_into_channel() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 64).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(2);
+        let task = tokio::task::spawn_blocking(move || {
+            let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+            decoder.read_range_into_channel(100, 900, sender)
+        });
+
+        let mut output = Vec::new();
+        while let Some(chunk) = receiver.recv().await {
+            assert!(chunk.len() <= 64);
+            output.extend(chunk);
+        }
+        task.await.unwrap().unwrap();
+        assert_eq!(output, &data[100..900]);
+    }
+
+    #[test]
+    fn test_decompression_speed_estimate() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4096).unwrap();
+        encoder.write_all(&vec![7u8; 64 * 1024]).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.estimated_decompression_speed_mb_per_sec(), None);
+        decoder.read_range(0, 64 * 1024).unwrap();
+        let speed = decoder.estimated_decompression_speed_mb_per_sec().unwrap();
+        assert!(speed > 0.0);
+        assert_eq!(decoder.stats().decompression_speed_mb_per_sec, Some(speed));
+
+        // 1 MB in 1 s, then 2 MB in 1 s, moves the average a fifth of the way
+        let mut stats = DecoderStats::default();
+        stats.record_speed(1_000_000, Duration::from_secs(1));
+        stats.record_speed(2_000_000, Duration::from_secs(1));
+        let average = stats.decompression_speed_mb_per_sec.unwrap();
+        assert!((average - 1.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_exact_frame_stats() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.stats(), DecoderStats::default());
+
+        assert_eq!(decoder.read_range(8, 16).unwrap(), &data[8..16]);
+        assert_eq!(decoder.read_range(8, 24).unwrap(), &data[8..24]);
+        assert_eq!(decoder.read_range(9, 16).unwrap(), &data[9..16]);
+        assert_eq!(decoder.read_range(40, 45).unwrap(), &data[40..45]);
+
+        let stats = decoder.stats();
+        assert_eq!(stats.reads, 4);
+        assert_eq!(stats.exact_frame_reads, 3);
+        assert!((stats.exact_frame_hit_rate() - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_sequential_reads_reuse_frames() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 1024).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        for start in (0..4000).step_by(100) {
+            let end = start + 100;
+            assert_eq!(
+                decoder.read_range(start as u64, end as u64).unwrap(),
+                &data[start..end]
+            );
+        }
+        let stats = decoder.stats();
+        assert_eq!(stats.reads, 40);
+        // Only the first read and the three that straddle a frame boundary
+        // decompress; the rest reuse the frames decoded before them
+        assert_eq!(stats.frame_cache_hits, 36);
+
+        // A whole-frame read is not kept, and the kept frames are unchanged
+        assert_eq!(decoder.read_range(0, 1024).unwrap(), &data[..1024]);
+        assert_eq!(decoder.read_range(4000, 4096).unwrap(), &data[4000..]);
+        assert_eq!(decoder.stats().frame_cache_hits, 37);
+        assert_eq!(decoder.read_range(1500, 1600).unwrap(), &data[1500..1600]);
+        assert_eq!(decoder.stats().frame_cache_hits, 37);
+    }
+
+    #[test]
+    fn test_frame_entropy_estimate() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4096).unwrap();
+        encoder.write_all(&[0u8; 4096]).unwrap();
+        // A simple LCG gives bytes that zstd cannot compress
+        let mut state = 1u32;
+        let noise: Vec<u8> = (0..4096)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                state.to_be_bytes()[0]
+            })
+            .collect();
+        encoder.write_all(&noise).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let zeros = decoder.frame_entropy_estimate(0).unwrap();
+        let random = decoder.frame_entropy_estimate(1).unwrap();
+        assert!(zeros < 0.1, "zeros estimated at {zeros}");
+        assert!(random > 0.9, "noise estimated at {random}");
+        assert!(random <= 1.0);
+        assert!(decoder.frame_entropy_estimate(2).is_err());
+    }
+
+    #[cfg(feature = "xxhash")]
+    #[test]
+    fn test_read_range_with_checksums() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4).unwrap();
+        encoder.write_all(b"aaaabbbbcc").unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let (data, checksums) = decoder.read_range_with_checksums(2, 9).unwrap();
+        assert_eq!(data, b"aabbbbc");
+        let expected: Vec<FrameChecksum> = [&b"aaaa"[..], b"bbbb", b"cc"]
+            .iter()
+            .zip(0..)
+            .map(|(frame, frame_index)| FrameChecksum {
+                frame_index,
+                xxhash: xxhash_rust::xxh64::xxh64(frame, 0),
+            })
+            .collect();
+        assert_eq!(checksums, expected);
+        assert_eq!(decoder.current_position(), 9);
+
+        let (data, checksums) = decoder.read_range_with_checksums(5, 100).unwrap();
+        assert_eq!(data, b"bbbcc");
+        assert_eq!(checksums, expected[1..]);
+
+        let (data, checksums) = decoder.read_range_with_checksums(3, 3).unwrap();
+        assert!(data.is_empty() && checksums.is_empty());
+        assert!(decoder.read_range_with_checksums(10, 12).is_err());
+    }
+
+    #[test]
+    fn test_read_frame_rev() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4).unwrap();
+        encoder.write_all(b"aaaabbbbcc").unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.read_frame(1).unwrap(), b"bbbb");
+        assert_eq!(decoder.read_frame_rev(0).unwrap(), b"cc");
+        assert_eq!(decoder.read_frame_rev(2).unwrap(), b"aaaa");
+        assert!(decoder.read_frame_rev(3).is_err());
+        assert!(decoder.read_frame(3).is_err());
+
+        let frames: Vec<Vec<u8>> = decoder.frame_iter_rev().map(Result::unwrap).collect();
+        assert_eq!(
+            frames,
+            vec![b"cc".to_vec(), b"bbbb".to_vec(), b"aaaa".to_vec()]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_range_into_file() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4).unwrap();
+        encoder.write_all(b"aaaabbbbccccdd").unwrap();
+        encoder.finish().unwrap();
+
+        let path = std::env::temp_dir().join(format!(
+            "seekable-zstd-into-file-{}.bin",
+            std::process::id()
+        ));
+        let output = File::create(&path).unwrap();
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        // Fill the second half first to show the writes are positioned
+        assert_eq!(decoder.read_range_into_file(7, 14, &output, 7).unwrap(), 7);
+        assert_eq!(decoder.read_range_into_file(0, 7, &output, 0).unwrap(), 7);
+        drop(output);
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"aaaabbbbccccdd");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_decode_json_frames() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::new(&mut buffer).unwrap();
+        let frames: [&[u8]; 3] = [br#"{"id":1}"#, b"[2,3]", b"not json"];
+        encoder.compress_blocks(frames).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(
+            decoder.decode_frame_as_json(0).unwrap(),
+            serde_json::json!({"id": 1})
+        );
+        assert!(matches!(
+            decoder.decode_frame_as_json(2),
+            Err(Error::Format(_))
+        ));
+        // Bytes 3..9 touch the first two frames
+        assert_eq!(
+            decoder.decode_range_as_json_array(3, 9).unwrap(),
+            vec![serde_json::json!({"id": 1}), serde_json::json!([2, 3])]
+        );
+        assert!(decoder.decode_range_as_json_array(0, 100).is_err());
+        assert!(decoder.decode_range_as_json_array(5, 5).unwrap().is_empty());
+        assert!(matches!(
+            decoder.decode_range_as_json_array(100, 200),
+            Err(Error::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_frame_count_in_range() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4).unwrap();
+        encoder.write_all(b"aaaabbbbccccdd").unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.frame_count_in_range(0, 4), 1);
+        assert_eq!(decoder.frame_count_in_range(5, 7), 1);
+        assert_eq!(decoder.frame_count_in_range(3, 5), 2);
+        assert_eq!(decoder.frame_count_in_range(0, 14), 4);
+        assert_eq!(decoder.frame_count_in_range(1, 100), 4);
+        assert_eq!(decoder.frame_count_in_range(6, 6), 0);
+        assert_eq!(decoder.frame_count_in_range(14, 20), 0);
+    }
+
+    #[test]
+    fn test_read_range_prefixed() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4).unwrap();
+        encoder.write_all(b"aaaabbbbcc").unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let result = decoder.read_range_prefixed(2, 6, &[0x04]).unwrap();
+        assert_eq!(result, b"\x04aabb");
+        assert_eq!(result.capacity(), 5);
+        assert_eq!(
+            decoder.read_range_prefixed(8, 100, b"len:").unwrap(),
+            b"len:cc"
+        );
+        assert_eq!(decoder.read_range_prefixed(3, 3, b"x").unwrap(), b"x");
+        assert!(decoder.read_range_prefixed(5, 3, b"x").is_err());
+        assert!(matches!(
+            decoder.read_range_prefixed(12, 20, b"x"),
+            Err(Error::OutOfBounds {
+                offset: 12,
+                size: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn test_dictionary_roundtrip() {
+        let records: Vec<String> = (0..500)
+            .map(|i| {
+                format!(
+                    r#"{{"id":{i},"kind":"sensor","status":"ok","value":{}}}"#,
+                    i % 7
+                )
+            })
+            .collect();
+        let dictionary: String = records.iter().step_by(50).map(String::as_str).collect();
+
+        let encode = |dictionary: Option<&[u8]>| {
+            let mut buffer = Vec::new();
+            let mut encoder =
+                Encoder::new_with_options_and_dictionary(&mut buffer, 4096, 3, dictionary).unwrap();
+            encoder
+                .compress_blocks(records.iter().map(String::as_bytes))
+                .unwrap();
+            encoder.finish().unwrap();
+            buffer
+        };
+        let plain = encode(None);
+        let with_dictionary = encode(Some(dictionary.as_bytes()));
+        assert!(with_dictionary.len() < plain.len());
+
+        let mut decoder =
+            Decoder::new_with_dictionary(Cursor::new(with_dictionary), dictionary.as_bytes())
+                .unwrap();
+        assert_eq!(decoder.frame_count(), 500);
+        assert_eq!(decoder.read_frame(123).unwrap(), records[123].as_bytes());
+        assert_eq!(
+            decoder.read_range(0, decoder.size()).unwrap(),
+            records.concat().as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_into_stream() {
+        use std::io::BufRead;
+
+        let text = "first line\nsecond line\nthird line, a little longer\nlast\n";
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let mut reader = std::io::BufReader::new(decoder.into_stream());
+        let lines: Vec<String> = reader.by_ref().lines().map(Result::unwrap).collect();
+        assert_eq!(
+            lines,
+            [
+                "first line",
+                "second line",
+                "third line, a little longer",
+                "last"
+            ]
+        );
+
+        // Seeking moves the cursor in decompressed offsets
+        let mut stream = reader.into_inner();
+        stream.seek(SeekFrom::Start(6)).unwrap();
+        let mut word = [0u8; 4];
+        stream.read_exact(&mut word).unwrap();
+        assert_eq!(&word, b"line");
+        assert_eq!(stream.seek(SeekFrom::Current(-4)).unwrap(), 6);
+        assert_eq!(
+            stream.seek(SeekFrom::End(-5)).unwrap(),
+            text.len() as u64 - 5
+        );
+        let mut tail = String::new();
+        stream.read_to_string(&mut tail).unwrap();
+        assert_eq!(tail, "last\n");
+
+        assert!(stream.seek(SeekFrom::Current(-100)).is_err());
+        assert_eq!(
+            stream.seek(SeekFrom::End(10)).unwrap(),
+            text.len() as u64 + 10
+        );
+        assert_eq!(stream.read(&mut word).unwrap(), 0);
+        assert_eq!(stream.into_inner().current_position(), text.len() as u64);
+    }
+
+    #[test]
+    fn test_read_at_fills_buffer() {
+        let data: Vec<u8> = (0..100u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 40).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        // Spans the boundary between the first and second frames
+        let mut buf = [0u8; 30];
+        assert_eq!(decoder.read_at(&mut buf, 25).unwrap(), 30);
+        assert_eq!(buf, data[25..55]);
+
+        // Only a short count at the end of the archive
+        assert_eq!(decoder.read_at(&mut buf, 90).unwrap(), 10);
+        assert_eq!(buf[..10], data[90..]);
+        assert_eq!(decoder.read_at(&mut buf, 100).unwrap(), 0);
+        assert_eq!(decoder.read_at(&mut buf, 500).unwrap(), 0);
+        assert_eq!(decoder.read_at(&mut [], 10).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_frames() {
+        let data: Vec<u8> = (0..2500u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 1000).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = Decoder::new(Cursor::new(buffer.clone())).unwrap();
+        let frames = decoder.frames();
+        let bounds: Vec<(u64, u64)> = frames
+            .iter()
+            .map(|frame| (frame.decomp_start, frame.decomp_end))
+            .collect();
+        assert_eq!(bounds, vec![(0, 1000), (1000, 2000), (2000, 2500)]);
+        for (i, frame) in frames.iter().enumerate() {
+            assert_eq!(frame.index, i as u64);
+            let end = frame.comp_start + frame.comp_size;
+            // Each compressed frame starts with the zstd magic number
+            assert_eq!(
+                buffer[usize::try_from(frame.comp_start).unwrap()..][..4],
+                [0x28, 0xB5, 0x2F, 0xFD]
+            );
+            if let Some(next) = frames.get(i + 1) {
+                assert_eq!(next.comp_start, end);
+            }
+        }
+
+        let parallel =
+            crate::parallel::ParallelDecoder::from_bytes(std::sync::Arc::new(buffer)).unwrap();
+        assert_eq!(parallel.frames().unwrap(), frames);
+    }
+
+    #[test]
+    fn test_size_compressed() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4).unwrap();
+        encoder.write_all(b"aaaabbbbccccdd").unwrap();
+        encoder.finish().unwrap();
+        let len = buffer.len() as u64;
+
+        let decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.size_compressed(), len);
+
+        let mut empty = Vec::new();
+        Encoder::new(&mut empty).unwrap().finish().unwrap();
+        let len = empty.len() as u64;
+        let decoder = Decoder::new(Cursor::new(empty)).unwrap();
+        assert_eq!(decoder.size_compressed(), len);
+    }
+
+    #[test]
+    fn test_read_range_partial() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 4).unwrap();
+        encoder.write_all(b"aaaabbbbcc").unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let full = decoder.read_range_partial(2, 10).unwrap();
+        assert_eq!(full.data(), b"aabbbbcc");
+        assert!(!full.is_truncated());
+
+        let partial = decoder.read_range_partial(6, 20).unwrap();
+        assert!(partial.is_truncated());
+        assert_eq!(partial.into_data(), b"bbcc");
+
+        assert!(decoder.read_range_partial(12, 20).is_err());
+    }
+
+    #[test]
+    fn test_read_ranges_deduplicates() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let results = decoder
+            .read_ranges(&[(0, 10), (4, 12), (0, 10), (0, 10)])
+            .unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_slice(), &data[0..10]);
+        assert_eq!(results[1].as_slice(), &data[4..12]);
+        assert!(Arc::ptr_eq(&results[0], &results[2]));
+        assert!(Arc::ptr_eq(&results[0], &results[3]));
+        assert_eq!(decoder.stats().reads, 2);
+    }
+
+    #[test]
+    fn test_read_range_lazy() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let mut lazy = decoder.read_range_lazy(6, 20);
+        assert!(!lazy.is_loaded());
+        assert_eq!(lazy.as_slice().unwrap(), &data[6..20]);
+        assert!(lazy.is_loaded());
+        assert_eq!(lazy.as_slice().unwrap(), &data[6..20]);
+        assert_eq!(decoder.stats().reads, 1);
+
+        assert!(decoder.read_range_lazy(100, 200).as_slice().is_err());
+        assert!(decoder.read_range_lazy(5, 5).as_slice().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_position_tracking() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 8).unwrap();
+        let data = b"Hello World, this is a test of seekable zstd.";
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.current_position(), 0);
+
+        decoder.read_range(6, 11).unwrap();
+        assert_eq!(decoder.current_position(), 11);
+
+        decoder.seek_to(20).unwrap();
+        assert_eq!(decoder.current_position(), 20);
+
+        assert!(decoder.seek_to(data.len() as u64 + 1).is_err());
+        assert_eq!(decoder.current_position(), 20);
     }
 }
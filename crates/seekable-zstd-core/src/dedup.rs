@@ -0,0 +1,196 @@
+use crate::error::Error;
+use crate::parallel::ParallelDecoder;
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, Weak};
+
+/// A read that is still being decoded; other callers wait on it.
+#[derive(Default)]
+struct Pending {
+    result: Mutex<Option<Result<Arc<Vec<u8>>, Error>>>,
+    done: Condvar,
+}
+
+impl Pending {
+    fn complete(&self, result: &Result<Arc<Vec<u8>>, Error>) {
+        let result = match result {
+            Ok(data) => Ok(Arc::clone(data)),
+            Err(err) => Err(err.duplicate()),
+        };
+        *self.result.lock().unwrap_or_else(PoisonError::into_inner) = Some(result);
+        self.done.notify_all();
+    }
+
+    fn wait(&self) -> Result<Arc<Vec<u8>>, Error> {
+        let mut result = self.result.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            match result.as_ref() {
+                Some(Ok(data)) => return Ok(Arc::clone(data)),
+                Some(Err(err)) => return Err(err.duplicate()),
+                None => {
+                    result = self
+                        .done
+                        .wait(result)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+            }
+        }
+    }
+}
+
+enum Slot {
+    Pending(Arc<Pending>),
+    Ready(Weak<Vec<u8>>),
+}
+
+/// A [`ParallelDecoder`] that shares results between callers reading the
+/// same range.
+///
+/// If a range is already being decoded, later callers for the exact same
+/// `(start, end)` block until it finishes and receive the same `Arc`. A
+/// finished result stays shared for as long as any caller still holds it;
+/// nothing is cached beyond that. Only identical ranges are merged, not
+/// overlapping ones.
+pub struct DeduplicatingDecoder {
+    inner: ParallelDecoder,
+    slots: Mutex<HashMap<(u64, u64), Slot>>,
+}
+
+/// Resolves a pending read even if decoding panics, so waiters never hang.
+struct Leader<'a> {
+    decoder: &'a DeduplicatingDecoder,
+    key: (u64, u64),
+    pending: Arc<Pending>,
+    finished: bool,
+}
+
+impl Leader<'_> {
+    fn finish(mut self, result: &Result<Arc<Vec<u8>>, Error>) {
+        let mut slots = self.decoder.lock();
+        match result {
+            Ok(data) => {
+                slots.insert(self.key, Slot::Ready(Arc::downgrade(data)));
+            }
+            Err(_) => {
+                slots.remove(&self.key);
+            }
+        }
+        drop(slots);
+        self.pending.complete(result);
+        self.finished = true;
+    }
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.decoder.lock().remove(&self.key);
+            self.pending
+                .complete(&Err(Error::Format("Shared read was aborted".to_string())));
+        }
+    }
+}
+
+impl DeduplicatingDecoder {
+    #[must_use]
+    pub fn new(inner: ParallelDecoder) -> Self {
+        Self {
+            inner,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn inner(&self) -> &ParallelDecoder {
+        &self.inner
+    }
+
+    /// Reads `start..end`, sharing the result with concurrent callers
+    /// reading the same range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the read fails. Callers that waited on a failed
+    /// read receive a copy of the same error.
+    pub fn read_range(&self, start: u64, end: u64) -> Result<Arc<Vec<u8>>, Error> {
+        let key = (start, end);
+        let mut slots = self.lock();
+        match slots.get(&key) {
+            Some(Slot::Pending(pending)) => {
+                let pending = Arc::clone(pending);
+                drop(slots);
+                return pending.wait();
+            }
+            Some(Slot::Ready(data)) => {
+                if let Some(data) = data.upgrade() {
+                    return Ok(data);
+                }
+            }
+            None => {}
+        }
+
+        // Drop results nobody holds any more before adding a new entry
+        slots.retain(|_, slot| match slot {
+            Slot::Pending(_) => true,
+            Slot::Ready(data) => data.strong_count() > 0,
+        });
+        let pending = Arc::new(Pending::default());
+        slots.insert(key, Slot::Pending(Arc::clone(&pending)));
+        drop(slots);
+
+        let leader = Leader {
+            decoder: self,
+            key,
+            pending,
+            finished: false,
+        };
+        let result = self
+            .inner
+            .read_ranges(&[key])
+            .map(|mut results| Arc::new(results.pop().unwrap_or_default()));
+        leader.finish(&result);
+        result
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<(u64, u64), Slot>> {
+        self.slots.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::Encoder;
+
+    #[test]
+    fn test_shared_results() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder =
+            DeduplicatingDecoder::new(ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap());
+
+        let first = decoder.read_range(100, 2000).unwrap();
+        let second = decoder.read_range(100, 2000).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.as_slice(), &data[100..2000]);
+
+        // Once every holder is gone the result is decoded again
+        drop((first, second));
+        let third = decoder.read_range(100, 2000).unwrap();
+        assert_eq!(third.as_slice(), &data[100..2000]);
+
+        let results: Vec<Arc<Vec<u8>>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| decoder.read_range(0, 4096).unwrap()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert!(results.iter().all(|r| r.as_slice() == data.as_slice()));
+
+        assert!(decoder.read_range(5000, 6000).is_err());
+        assert!(decoder.read_range(5000, 6000).is_err());
+    }
+}
@@ -1,14 +1,86 @@
+use crate::decoder::Decoder;
 use crate::error::Error;
-use std::io::Write;
+use crate::seek_table::SeekTable;
+use std::fs::{File, OpenOptions};
+use std::hash::{DefaultHasher, Hasher as _};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 use zeekstd::{EncodeOptions, FrameSizePolicy};
 
 pub const DEFAULT_FRAME_SIZE: usize = 256 * 1024;
 
+/// Estimates produced by [`Encoder::dry_run`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DryRunReport {
+    /// Number of frames the archive would contain.
+    pub frame_count: u64,
+    /// Total archive size in bytes, including the seek table.
+    pub estimated_compressed_size: u64,
+    /// Uncompressed size divided by compressed size.
+    pub estimated_compression_ratio: f64,
+}
+
+/// How thoroughly [`Encoder::append_with_validation`] checks the existing
+/// archive before writing to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AppendValidation {
+    /// Only parse the seek table, as [`Encoder::append`] does.
+    #[default]
+    None,
+    /// Check that the seek table accounts for the whole file and that every
+    /// frame starts with the zstd magic number, as
+    /// [`Decoder::new_verified`] does. Nothing is decompressed.
+    SeekTableOnly,
+    /// Check the seek table, then decompress every existing frame.
+    FullVerify,
+}
+
 pub struct Encoder<'a, W: Write> {
     inner: zeekstd::Encoder<'a, W>,
+    checkpoint_every: Option<u64>,
+    checkpoint_bytes: u64,
+    /// Decompressed bytes written so far.
+    written: u64,
+    /// End the current frame before the next byte is written.
+    frame_end_pending: bool,
+    /// Set when appending to an existing archive; see [`Encoder::append`].
+    append: Option<Append>,
+    /// Set when the output file can be read back; see [`Encoder::finish_and_verify`].
+    readback: Option<Readback>,
 }
 
-impl<W: Write> Encoder<'_, W> {
+/// A readable handle to the output file, and a hash of everything written
+/// by this encoder, for checking the archive after it is finished.
+struct Readback {
+    file: File,
+    /// Decompressed offset at which this encoder's data starts.
+    start: u64,
+    hasher: HashWriter,
+}
+
+/// Feeds written bytes into a `DefaultHasher`.
+#[derive(Default)]
+struct HashWriter(DefaultHasher);
+
+impl Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The existing archive's seek table, and a second handle to the file for
+/// writing the merged seek table on finish.
+struct Append {
+    seek_table: SeekTable,
+    file: File,
+}
+
+impl<'a, W: Write> Encoder<'a, W> {
     /// Creates a new `Encoder` with default configuration.
     ///
     /// # Errors
@@ -20,6 +92,11 @@ impl<W: Write> Encoder<'_, W> {
 
     /// Creates a new `Encoder` with a custom frame size.
     ///
+    /// `frame_size` bounds the uncompressed bytes per frame, so every frame
+    /// decompresses to exactly `frame_size` bytes except the last. See
+    /// [`with_compressed_frame_size`](Self::with_compressed_frame_size) to
+    /// bound the compressed size instead.
+    ///
     /// # Errors
     ///
     /// Returns an error if the encoder cannot be initialized.
@@ -27,11 +104,35 @@ impl<W: Write> Encoder<'_, W> {
         let frame_size_u32 = u32::try_from(frame_size)
             .map_err(|_| Error::Format("Frame size too large".to_string()))?;
 
-        let options =
-            EncodeOptions::new().frame_size_policy(FrameSizePolicy::Uncompressed(frame_size_u32));
+        Self::with_frame_size_policy(writer, FrameSizePolicy::Uncompressed(frame_size_u32))
+    }
+
+    /// Creates a new `Encoder` that bounds the compressed bytes per frame.
+    ///
+    /// A new frame is started once the current frame's compressed output
+    /// reaches `compressed_frame_size`, which keeps frames close to a storage
+    /// block size regardless of how well the data compresses. The bound is
+    /// approximate: zstd emits output in blocks, so a frame can overshoot it
+    /// by up to one block. Uncompressed frame sizes then vary with the data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoder cannot be initialized.
+    pub fn with_compressed_frame_size(
+        writer: W,
+        compressed_frame_size: usize,
+    ) -> Result<Self, Error> {
+        let frame_size_u32 = u32::try_from(compressed_frame_size)
+            .map_err(|_| Error::Format("Frame size too large".to_string()))?;
+
+        Self::with_frame_size_policy(writer, FrameSizePolicy::Compressed(frame_size_u32))
+    }
+
+    fn with_frame_size_policy(writer: W, policy: FrameSizePolicy) -> Result<Self, Error> {
+        let options = EncodeOptions::new().frame_size_policy(policy);
 
         let inner = options.into_encoder(writer).map_err(Error::from)?;
-        Ok(Self { inner })
+        Ok(Self::from_inner(inner))
     }
 
     /// Creates a new `Encoder` with a custom compression level.
@@ -44,7 +145,7 @@ impl<W: Write> Encoder<'_, W> {
         let options = EncodeOptions::new().compression_level(level);
 
         let inner = options.into_encoder(writer).map_err(Error::from)?;
-        Ok(Self { inner })
+        Ok(Self::from_inner(inner))
     }
 
     /// Creates a new `Encoder` with custom options.
@@ -53,15 +154,110 @@ impl<W: Write> Encoder<'_, W> {
     ///
     /// Returns an error if the encoder cannot be initialized.
     pub fn new_with_options(writer: W, frame_size: usize, level: i32) -> Result<Self, Error> {
+        Self::new_with_options_and_dictionary(writer, frame_size, level, None)
+    }
+
+    /// Creates a new `Encoder` that compresses every frame with `dictionary`.
+    ///
+    /// Frames cannot share context with each other, so archives of many
+    /// small, similar records (small frames or one record per block)
+    /// compress poorly on their own. A dictionary, either one trained with
+    /// `zstd --train` or just representative sample content, gives every
+    /// frame that shared context. The archive can only be read back by
+    /// passing the same dictionary to [`Decoder::new_with_dictionary`];
+    /// nothing in the archive says that a dictionary was used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dictionary cannot be loaded or the encoder
+    /// cannot be initialized.
+    pub fn with_dictionary(writer: W, dictionary: &[u8]) -> Result<Self, Error> {
+        Self::new_with_options_and_dictionary(
+            writer,
+            DEFAULT_FRAME_SIZE,
+            zstd_safe::CLEVEL_DEFAULT,
+            Some(dictionary),
+        )
+    }
+
+    /// Creates a new `Encoder` with custom options and an optional
+    /// dictionary.
+    ///
+    /// With `None` this is [`new_with_options`](Self::new_with_options); see
+    /// [`with_dictionary`](Self::with_dictionary) for what a dictionary
+    /// means for reading the archive back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dictionary cannot be loaded or the encoder
+    /// cannot be initialized.
+    pub fn new_with_options_and_dictionary(
+        writer: W,
+        frame_size: usize,
+        level: i32,
+        dictionary: Option<&[u8]>,
+    ) -> Result<Self, Error> {
         let frame_size_u32 = u32::try_from(frame_size)
             .map_err(|_| Error::Format("Frame size too large".to_string()))?;
 
-        let options = EncodeOptions::new()
+        let mut options = EncodeOptions::new()
             .frame_size_policy(FrameSizePolicy::Uncompressed(frame_size_u32))
             .compression_level(level);
+        if let Some(dictionary) = dictionary {
+            let mut cctx = zstd_safe::CCtx::try_create()
+                .ok_or_else(|| Error::Zstd("Failed to create compression context".to_string()))?;
+            // Loaded dictionaries stay active across the per-frame resets
+            cctx.load_dictionary(dictionary)
+                .map_err(|code| Error::Zstd(zstd_safe::get_error_name(code).to_string()))?;
+            options = options.cctx(cctx);
+        }
 
         let inner = options.into_encoder(writer).map_err(Error::from)?;
-        Ok(Self { inner })
+        Ok(Self::from_inner(inner))
+    }
+
+    fn from_inner(inner: zeekstd::Encoder<'a, W>) -> Self {
+        Self {
+            inner,
+            checkpoint_every: None,
+            checkpoint_bytes: 0,
+            written: 0,
+            frame_end_pending: false,
+            append: None,
+            readback: None,
+        }
+    }
+
+    /// Starts a new frame automatically after every `n` uncompressed bytes.
+    ///
+    /// This applies on top of the frame size policy, and both end frames
+    /// independently. Checkpoints count the bytes written since the last
+    /// checkpoint or [`end_frame`](Self::end_frame), not since the current
+    /// frame started: frames the size policy ends in between do not restart
+    /// the count, so with both limits in play checkpoint frames line up with
+    /// multiples of `n` rather than with frame starts. Passing `0` disables
+    /// automatic checkpoints.
+    #[must_use]
+    pub fn write_checkpoint_every_n_bytes(mut self, n: u64) -> Self {
+        self.checkpoint_every = (n > 0).then_some(n);
+        self.checkpoint_bytes = 0;
+        self
+    }
+
+    /// Ends the current frame, so the next byte written starts a new one.
+    ///
+    /// This applies regardless of the frame size policy, which still splits
+    /// frames that grow past the frame size. Writing each record followed by
+    /// `end_frame` puts record `N` at the start of frame `N`, as long as no
+    /// record is larger than the frame size.
+    ///
+    /// The frame is closed just before the next byte, so calling this twice
+    /// in a row, before writing anything, or right before
+    /// [`finish`](Self::finish) never produces an empty frame.
+    pub fn end_frame(&mut self) {
+        if self.written > 0 {
+            self.frame_end_pending = true;
+        }
     }
 
     /// Writes all data to the encoder.
@@ -70,23 +266,586 @@ impl<W: Write> Encoder<'_, W> {
     ///
     /// Returns an error if the write fails.
     pub fn write_all(&mut self, data: &[u8]) -> Result<(), Error> {
-        self.inner.write_all(data).map_err(Error::from)
+        Write::write_all(self, data).map_err(Error::from)
+    }
+
+    /// Compresses each block into its own frame and returns the decompressed
+    /// start offset of every block.
+    ///
+    /// Any data written before the first block is closed off into a separate
+    /// frame, and data written afterwards starts a new frame. A block larger
+    /// than the frame size is still split by the frame size policy, so for a
+    /// strict one-block-per-frame layout (as [`Decoder::read_block`] expects)
+    /// keep blocks no larger than the frame size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Format` for an empty block, which would not produce a
+    /// frame, or an error if compression fails. Blocks before the failing one
+    /// have already been written.
+    ///
+    /// [`Decoder::read_block`]: crate::Decoder::read_block
+    pub fn compress_blocks<'b, I>(&mut self, blocks: I) -> Result<Vec<u64>, Error>
+    where
+        I: IntoIterator<Item = &'b [u8]>,
+    {
+        let mut offsets = Vec::new();
+        for (index, block) in blocks.into_iter().enumerate() {
+            if block.is_empty() {
+                return Err(Error::Format(format!("Block {index} is empty")));
+            }
+            if self.written > 0 {
+                self.frame_end_pending = true;
+            }
+            offsets.push(self.written);
+            self.write_all(block)?;
+        }
+        if !offsets.is_empty() {
+            self.frame_end_pending = true;
+        }
+        Ok(offsets)
+    }
+
+    /// Compresses several sources into one archive, one after another.
+    ///
+    /// The sources are concatenated logically: each one's data follows the
+    /// previous one's directly, without forcing a frame boundary between
+    /// them. Returns the decompressed start offset of each source, so source
+    /// `i` occupies `offsets[i]..offsets[i + 1]` (or up to the archive size
+    /// for the last one). Empty sources get an offset equal to the next one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading a source, compressing, or writing the
+    /// output fails.
+    pub fn compress_from_readers<I>(
+        readers: I,
+        writer: W,
+        frame_size: usize,
+        level: i32,
+    ) -> Result<Vec<u64>, Error>
+    where
+        I: IntoIterator<Item = Box<dyn Read>>,
+    {
+        let mut encoder = Self::new_with_options(writer, frame_size, level)?;
+        let mut offsets = Vec::new();
+        for mut reader in readers {
+            offsets.push(encoder.written);
+            std::io::copy(&mut reader, &mut encoder)?;
+        }
+        encoder.finish()?;
+        Ok(offsets)
     }
 
     /// Finishes the stream and returns the underlying writer.
     ///
+    /// For an encoder created by [`append`](Encoder::append), this writes the
+    /// merged seek table and returns the total size of the archive.
+    ///
     /// # Errors
     ///
     /// Returns an error if the finish operation fails.
-    pub fn finish(self) -> Result<u64, Error> {
-        self.inner.finish().map_err(Error::from)
+    pub fn finish(mut self) -> Result<u64, Error> {
+        match self.append.take() {
+            Some(append) => self.finish_append(append),
+            None => self.inner.finish().map_err(Error::from),
+        }
+    }
+
+    /// Finishes the archive, then decompresses what this encoder wrote and
+    /// compares it with the input.
+    ///
+    /// This costs one extra decompression pass over the new data, and
+    /// catches archives that were corrupted while being written. Only
+    /// encoders created with [`create`](Encoder::create) or
+    /// [`append`](Encoder::append) can read their output back.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Format("post-encode verification failed")` if the
+    /// decompressed data does not match what was written, an error if the
+    /// encoder cannot read its output back, or any error from
+    /// [`finish`](Self::finish).
+    pub fn finish_and_verify(mut self) -> Result<u64, Error> {
+        let Some(readback) = self.readback.take() else {
+            return Err(Error::Format(
+                "Verification requires an encoder from Encoder::create or Encoder::append"
+                    .to_string(),
+            ));
+        };
+        let written = self.written;
+        let total = self.finish()?;
+
+        let mut decoder = Decoder::new(readback.file)?;
+        let mut output_hash = HashWriter::default();
+        let end = decoder.size();
+        let decoded_len = decoder.read_range_into_writer(readback.start, end, &mut output_hash)?;
+
+        if decoded_len != written || output_hash.0.finish() != readback.hasher.0.finish() {
+            return Err(Error::Format("post-encode verification failed".to_string()));
+        }
+        Ok(total)
+    }
+
+    fn finish_append(mut self, append: Append) -> Result<u64, Error> {
+        // Auto and checkpoint frame ends are deferred until the next byte, so
+        // the open frame holds data whenever anything was written.
+        if self.written > 0 {
+            self.inner.end_frame().map_err(Error::from)?;
+        }
+        self.inner.flush()?;
+
+        let appended = self.inner.into_seek_table();
+        let mut merged = append.seek_table.inner;
+        for index in 0..appended.num_frames() {
+            let compressed = appended.frame_size_comp(index).map_err(Error::from)?;
+            let decompressed = appended.frame_size_decomp(index).map_err(Error::from)?;
+            let (Ok(compressed), Ok(decompressed)) =
+                (u32::try_from(compressed), u32::try_from(decompressed))
+            else {
+                return Err(Error::Format("Frame size too large".to_string()));
+            };
+            merged
+                .log_frame(compressed, decompressed)
+                .map_err(Error::from)?;
+        }
+
+        // Both handles share one file offset, which is now past the new frames
+        let mut file = append.file;
+        let mut serializer = merged.into_serializer();
+        std::io::copy(&mut serializer, &mut file)?;
+        file.flush()?;
+        Ok(file.stream_position()?)
+    }
+}
+
+/// Checks an archive opened for appending at the requested level.
+fn validate_existing(file: &mut File, validation: AppendValidation) -> Result<(), Error> {
+    match validation {
+        AppendValidation::None => {}
+        AppendValidation::SeekTableOnly => {
+            Decoder::new_verified(file)?;
+        }
+        AppendValidation::FullVerify => {
+            let mut decoder = Decoder::new_verified(file)?;
+            let end = decoder.size();
+            decoder.read_range_into_writer(0, end, &mut std::io::sink())?;
+        }
+    }
+    Ok(())
+}
+
+impl Encoder<'static, File> {
+    /// Opens an existing archive for appending frames.
+    ///
+    /// The trailing seek table is read and truncated away, and new data is
+    /// compressed into frames after the existing ones. [`finish`](Self::finish)
+    /// writes a seek table covering both old and new frames. Existing frames
+    /// are never re-encoded. If the encoder is dropped without `finish`, the
+    /// file is left without a seek table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened, if it is not a valid
+    /// seekable archive, or if the encoder cannot be initialized.
+    pub fn append<P: AsRef<Path>>(path: P, frame_size: usize, level: i32) -> Result<Self, Error> {
+        Self::append_with_validation(path, frame_size, level, AppendValidation::None)
+    }
+
+    /// Opens an existing archive for appending after checking it is readable.
+    ///
+    /// Like [`append`](Self::append), but the existing archive is checked
+    /// at the given [`AppendValidation`] level first, so new frames are not
+    /// added to an archive that is already damaged. The file is not modified
+    /// if validation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Format` describing the problem if validation fails,
+    /// or any error [`append`](Self::append) would return.
+    pub fn append_with_validation<P: AsRef<Path>>(
+        path: P,
+        frame_size: usize,
+        level: i32,
+        validation: AppendValidation,
+    ) -> Result<Self, Error> {
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+        validate_existing(&mut file, validation).map_err(|err| match err {
+            Error::Io(err) => Error::Io(err),
+            Error::Zstd(msg) | Error::Format(msg) => {
+                Error::Format(format!("Existing archive failed validation: {msg}"))
+            }
+            err @ Error::OutOfBounds { .. } => {
+                Error::Format(format!("Existing archive failed validation: {err}"))
+            }
+        })?;
+        let seek_table = SeekTable::from_reader(&mut file)?;
+
+        // Set up the encoder before touching the file, so a failure leaves
+        // the archive and its seek table intact
+        let mut encoder = Self::new_with_options(file.try_clone()?, frame_size, level)?;
+        file.set_len(seek_table.compressed_size())?;
+        file.seek(SeekFrom::End(0))?;
+        encoder.readback = Some(Readback {
+            file: file.try_clone()?,
+            start: seek_table.size(),
+            hasher: HashWriter::default(),
+        });
+        encoder.append = Some(Append { seek_table, file });
+        Ok(encoder)
+    }
+
+    /// Opens an existing archive for appending, holding an exclusive lock.
+    ///
+    /// Like [`append`](Self::append), but first blocks until no other
+    /// process holds a lock on the file, then keeps readers that use
+    /// [`Decoder::open_with_lock`](crate::Decoder::open_with_lock) out until
+    /// the returned [`FileLock`](crate::FileLock) is dropped. Drop it after
+    /// [`finish`](Self::finish), once the new seek table is written. Unix
+    /// only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or locked, or any error
+    /// [`append`](Self::append) would return.
+    #[cfg(unix)]
+    pub fn open_with_exclusive_lock<P: AsRef<Path>>(
+        path: P,
+        frame_size: usize,
+        level: i32,
+    ) -> Result<(Self, crate::FileLock), Error> {
+        let lock = crate::FileLock::exclusive(&File::open(&path)?)?;
+        Ok((Self::append(path, frame_size, level)?, lock))
+    }
+
+    /// Creates (or truncates) the archive at `path` and opens it for writing.
+    ///
+    /// Unlike passing a `File` to [`new_with_options`](Encoder::new_with_options),
+    /// the file is opened readable as well, so the archive can be checked with
+    /// [`finish_and_verify`](Encoder::finish_and_verify).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or the encoder cannot
+    /// be initialized.
+    pub fn create<P: AsRef<Path>>(path: P, frame_size: usize, level: i32) -> Result<Self, Error> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let readback = file.try_clone()?;
+
+        let mut encoder = Self::new_with_options(file, frame_size, level)?;
+        encoder.readback = Some(Readback {
+            file: readback,
+            start: 0,
+            hasher: HashWriter::default(),
+        });
+        Ok(encoder)
+    }
+
+    /// Compresses `input` into a directory of independent seekable archives.
+    ///
+    /// Every `chunk_size` uncompressed bytes go into their own file, named
+    /// `chunk_000000.szst`, `chunk_000001.szst`, and so on, in `output_dir`.
+    /// Each file is a complete archive that can be opened on its own. Empty
+    /// input produces no files. Returns the created paths in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `chunk_size` is zero, if reading the input or
+    /// creating a file fails, or if compression fails. Files created before
+    /// the error are left in place.
+    pub fn split_and_compress(
+        mut input: impl Read,
+        output_dir: impl AsRef<Path>,
+        chunk_size: usize,
+        frame_size: usize,
+        level: i32,
+    ) -> Result<Vec<PathBuf>, Error> {
+        if chunk_size == 0 {
+            return Err(Error::Format("Chunk size must be non-zero".to_string()));
+        }
+
+        let mut paths = Vec::new();
+        let mut head = vec![0u8; chunk_size.min(64 * 1024)];
+        loop {
+            let mut chunk = input.by_ref().take(chunk_size as u64);
+            // Read ahead so that exhausted input does not create an empty file
+            let n = loop {
+                match chunk.read(&mut head) {
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                    result => break result?,
+                }
+            };
+            if n == 0 {
+                return Ok(paths);
+            }
+
+            let path = output_dir
+                .as_ref()
+                .join(format!("chunk_{:06}.szst", paths.len()));
+            let mut encoder = Self::new_with_options(File::create(&path)?, frame_size, level)?;
+            encoder.write_all(&head[..n])?;
+            std::io::copy(&mut chunk, &mut encoder)?;
+            encoder.finish()?;
+            paths.push(path);
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<W: Write + Send + 'static> Encoder<'static, W> {
+    /// Compresses everything read from an async reader into `writer`.
+    ///
+    /// Input is read `frame_size` bytes at a time and handed to a sync
+    /// `Encoder` running on tokio's blocking thread pool, so compression never
+    /// blocks the async runtime. Returns the total number of compressed bytes
+    /// written, as reported by [`finish`](Self::finish).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading the input, compressing, or writing the
+    /// output fails.
+    pub async fn compress_from_async_read<R>(
+        mut input: R,
+        writer: W,
+        frame_size: usize,
+        level: i32,
+    ) -> Result<u64, Error>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        use tokio::io::AsyncReadExt;
+
+        let (sender, compress) = Self::spawn_compressor(writer, frame_size, level);
+
+        let chunk_size = frame_size.max(1);
+        loop {
+            let mut chunk = Vec::with_capacity(chunk_size);
+            while chunk.len() < chunk_size {
+                if input.read_buf(&mut chunk).await? == 0 {
+                    break;
+                }
+            }
+            if chunk.is_empty() {
+                break;
+            }
+            // A closed channel means the encoder failed; its error is returned below
+            if sender.send(chunk).await.is_err() {
+                break;
+            }
+        }
+        drop(sender);
+
+        compress
+            .await
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?
+    }
+
+    /// Compresses every chunk yielded by an async stream into `writer`.
+    ///
+    /// Chunks are accumulated until `frame_size` bytes are buffered and then
+    /// handed to a sync `Encoder` on tokio's blocking thread pool. At most two
+    /// buffered frames wait for the encoder; beyond that the stream is not
+    /// polled, so a slow writer applies backpressure to the source. Returns
+    /// the total number of compressed bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if compressing or writing the output fails.
+    pub async fn compress_async_stream<S>(
+        mut stream: S,
+        writer: W,
+        frame_size: usize,
+        level: i32,
+    ) -> Result<u64, Error>
+    where
+        S: futures_core::Stream<Item = bytes::Bytes> + Unpin,
+    {
+        let (sender, compress) = Self::spawn_compressor(writer, frame_size, level);
+
+        let chunk_size = frame_size.max(1);
+        let mut chunk = Vec::with_capacity(chunk_size);
+        loop {
+            let next =
+                std::future::poll_fn(|cx| std::pin::Pin::new(&mut stream).poll_next(cx)).await;
+            let Some(mut bytes) = next else {
+                break;
+            };
+            while !bytes.is_empty() {
+                let take = bytes.len().min(chunk_size - chunk.len());
+                chunk.extend_from_slice(&bytes.split_to(take));
+                if chunk.len() == chunk_size {
+                    let full = std::mem::replace(&mut chunk, Vec::with_capacity(chunk_size));
+                    // A closed channel means the encoder failed; its error is returned below
+                    if sender.send(full).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            if sender.is_closed() {
+                break;
+            }
+        }
+        if !chunk.is_empty() {
+            let _ = sender.send(chunk).await;
+        }
+        drop(sender);
+
+        compress
+            .await
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?
+    }
+
+    /// Compresses every chunk received on a broadcast channel into `writer`.
+    ///
+    /// Chunks are compressed in the order the channel delivers them until
+    /// every sender has been dropped, so several producers holding clones of
+    /// one `broadcast::Sender` can feed a single archive. Compression runs on
+    /// tokio's blocking thread pool. Returns the total number of compressed
+    /// bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receiver lagged and missed messages, since the
+    /// archive would silently be missing data, or if compressing or writing
+    /// the output fails. On a lag the output holds a finished archive of
+    /// only the chunks received before it and should be discarded.
+    pub async fn compress_from_broadcast(
+        mut receiver: tokio::sync::broadcast::Receiver<bytes::Bytes>,
+        writer: W,
+        frame_size: usize,
+        level: i32,
+    ) -> Result<u64, Error> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let (sender, compress) = Self::spawn_compressor(writer, frame_size, level);
+
+        let mut lagged = None;
+        loop {
+            match receiver.recv().await {
+                Ok(bytes) => {
+                    // A closed channel means the encoder failed; its error is returned below
+                    if sender.send(bytes.to_vec()).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Closed) => break,
+                Err(RecvError::Lagged(skipped)) => {
+                    lagged = Some(skipped);
+                    break;
+                }
+            }
+        }
+        drop(sender);
+
+        let written = compress
+            .await
+            .map_err(|e| Error::Io(std::io::Error::other(e)))??;
+        match lagged {
+            Some(skipped) => Err(Error::Io(std::io::Error::other(format!(
+                "broadcast receiver lagged and missed {skipped} chunks"
+            )))),
+            None => Ok(written),
+        }
+    }
+
+    /// Starts a sync encoder on the blocking pool, fed through a channel.
+    fn spawn_compressor(
+        writer: W,
+        frame_size: usize,
+        level: i32,
+    ) -> (
+        tokio::sync::mpsc::Sender<Vec<u8>>,
+        tokio::task::JoinHandle<Result<u64, Error>>,
+    ) {
+        let (sender, mut receiver) = tokio::sync::mpsc::channel::<Vec<u8>>(2);
+        let compress = tokio::task::spawn_blocking(move || {
+            let mut encoder = Encoder::new_with_options(writer, frame_size, level)?;
+            while let Some(chunk) = receiver.blocking_recv() {
+                encoder.write_all(&chunk)?;
+            }
+            encoder.finish()
+        });
+        (sender, compress)
+    }
+}
+
+impl Encoder<'static, std::io::Sink> {
+    /// Encodes `data` without producing output and reports the resulting
+    /// frame count and archive size.
+    ///
+    /// This runs the real compressor, so it costs as much CPU as a full
+    /// encode, but nothing is written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the encoder cannot be initialized or compression
+    /// fails.
+    pub fn dry_run(data: &[u8], frame_size: usize, level: i32) -> Result<DryRunReport, Error> {
+        let frame_size_u32 = u32::try_from(frame_size)
+            .map_err(|_| Error::Format("Frame size too large".to_string()))?;
+
+        let mut inner = EncodeOptions::new()
+            .frame_size_policy(FrameSizePolicy::Uncompressed(frame_size_u32))
+            .compression_level(level)
+            .into_encoder(std::io::sink())
+            .map_err(Error::from)?;
+
+        inner.write_all(data)?;
+        inner.end_frame().map_err(Error::from)?;
+        inner.flush()?;
+
+        let compressed_frames = inner.written_compressed();
+        let seek_table = inner.into_seek_table();
+        let frame_count = u64::from(seek_table.num_frames());
+        let seek_table_size = seek_table.into_serializer().encoded_len() as u64;
+        let estimated_compressed_size = compressed_frames + seek_table_size;
+
+        #[allow(clippy::cast_precision_loss)]
+        let estimated_compression_ratio = data.len() as f64 / estimated_compressed_size as f64;
+
+        Ok(DryRunReport {
+            frame_count,
+            estimated_compressed_size,
+            estimated_compression_ratio,
+        })
     }
 }
 
 // Implement Write for Encoder
 impl<W: Write> Write for Encoder<'_, W> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.inner.write(buf)
+        // Frame ends are applied lazily, just before the next byte, so one
+        // that lands on the end of the input never leaves an empty frame.
+        if self.frame_end_pending && !buf.is_empty() {
+            self.inner
+                .end_frame()
+                .map_err(|e| Error::from(e).into_io_error())?;
+            self.frame_end_pending = false;
+            self.checkpoint_bytes = 0;
+        }
+
+        let limit = match self.checkpoint_every {
+            Some(every) => usize::try_from(every - self.checkpoint_bytes).unwrap_or(usize::MAX),
+            None => usize::MAX,
+        };
+        let written = self.inner.write(&buf[..buf.len().min(limit)])?;
+        if let Some(readback) = &mut self.readback {
+            readback.hasher.0.write(&buf[..written]);
+        }
+        self.written += written as u64;
+        self.checkpoint_bytes += written as u64;
+        if self
+            .checkpoint_every
+            .is_some_and(|every| self.checkpoint_bytes >= every)
+        {
+            self.frame_end_pending = true;
+        }
+        Ok(written)
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
@@ -107,4 +866,379 @@ mod tests {
 
         assert!(!buffer.is_empty());
     }
+
+    #[test]
+    fn test_checkpoint_every_n_bytes() {
+        use crate::decoder::Decoder;
+        use std::io::Cursor;
+
+        let data = vec![42u8; 1000];
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::new(&mut buffer)
+            .unwrap()
+            .write_checkpoint_every_n_bytes(250);
+        encoder.write_all(&data[..100]).unwrap();
+        encoder.write_all(&data[100..]).unwrap();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.frame_count(), 4);
+        assert_eq!(decoder.read_range(0, 1000).unwrap(), data);
+    }
+
+    #[test]
+    fn test_frame_size_policies() {
+        use crate::decoder::Decoder;
+        use std::io::Cursor;
+
+        let data = b"seekable zstd frame ".repeat(50_000);
+        let encode = |mut encoder: Encoder<'_, &mut Vec<u8>>| {
+            encoder.write_all(&data).unwrap();
+            encoder.finish().unwrap();
+        };
+
+        let mut uncompressed = Vec::new();
+        encode(Encoder::with_frame_size(&mut uncompressed, 64 * 1024).unwrap());
+        let uncompressed = Decoder::new(Cursor::new(uncompressed)).unwrap();
+        assert_eq!(uncompressed.frame_count(), 16);
+
+        // Highly compressible data packs far more than 64 KiB into 64 KiB of
+        // compressed output, so one frame holds everything
+        let mut compressed = Vec::new();
+        encode(Encoder::with_compressed_frame_size(&mut compressed, 64 * 1024).unwrap());
+        let mut compressed = Decoder::new(Cursor::new(compressed)).unwrap();
+        assert_eq!(compressed.frame_count(), 1);
+        assert_eq!(compressed.read_range(0, compressed.size()).unwrap(), data);
+
+        // Incompressible data fills the same bound after about 64 KiB of input
+        let mut state = 0x2545_f491_u32;
+        let noise: Vec<u8> = (0..data.len())
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state.to_le_bytes()[0]
+            })
+            .collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_compressed_frame_size(&mut buffer, 64 * 1024).unwrap();
+        encoder.write_all(&noise).unwrap();
+        encoder.finish().unwrap();
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert!(decoder.frame_count() > 1);
+        assert!(decoder.frame_count() <= uncompressed.frame_count());
+        assert_eq!(decoder.read_range(0, decoder.size()).unwrap(), noise);
+    }
+
+    #[test]
+    fn test_end_frame() {
+        use crate::decoder::Decoder;
+        use std::io::Cursor;
+
+        let records: [&[u8]; 3] = [b"first record", b"second", b"the third record"];
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::new(&mut buffer).unwrap();
+        // Nothing is open yet, so there is no frame to end
+        encoder.end_frame();
+        for record in records {
+            encoder.write_all(record).unwrap();
+            encoder.end_frame();
+        }
+        encoder.end_frame();
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.frame_count(), 3);
+        for (index, record) in records.into_iter().enumerate() {
+            assert_eq!(decoder.read_frame(index as u64).unwrap(), record);
+        }
+    }
+
+    #[test]
+    fn test_compress_blocks() {
+        use crate::decoder::Decoder;
+        use std::io::Cursor;
+
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::new(&mut buffer).unwrap();
+        encoder.write_all(b"header").unwrap();
+        let blocks: [&[u8]; 3] = [b"alpha", b"beta", b"gamma"];
+        let offsets = encoder.compress_blocks(blocks).unwrap();
+        assert_eq!(offsets, vec![6, 11, 15]);
+        assert!(encoder.compress_blocks([&b""[..]]).is_err());
+        encoder.finish().unwrap();
+
+        let mut decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.frame_count(), 4);
+        assert_eq!(decoder.read_block(2).unwrap(), b"beta");
+        assert_eq!(decoder.read_block(3).unwrap(), b"gamma");
+    }
+
+    #[test]
+    fn test_append() {
+        use crate::decoder::Decoder;
+
+        let path =
+            std::env::temp_dir().join(format!("seekable-zstd-append-{}.szst", std::process::id()));
+        let mut encoder = Encoder::with_frame_size(File::create(&path).unwrap(), 16).unwrap();
+        encoder.write_all(b"Hello World, ").unwrap();
+        encoder.finish().unwrap();
+
+        let mut encoder = Encoder::append(&path, 16, 3).unwrap();
+        encoder.write_all(b"this is appended data.").unwrap();
+        let written = encoder.finish().unwrap();
+        assert_eq!(written, std::fs::metadata(&path).unwrap().len());
+
+        let mut decoder = Decoder::open(&path).unwrap();
+        let expected = b"Hello World, this is appended data.";
+        assert_eq!(decoder.frame_count(), 3);
+        assert_eq!(decoder.read_range(0, 35).unwrap(), expected);
+        drop(decoder);
+
+        // Appending nothing keeps the archive unchanged
+        Encoder::append(&path, 16, 3).unwrap().finish().unwrap();
+        let mut decoder = Decoder::open(&path).unwrap();
+        assert_eq!(decoder.frame_count(), 3);
+        assert_eq!(decoder.read_range(0, 35).unwrap(), expected);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_compress_from_readers() {
+        use crate::decoder::Decoder;
+
+        let sources: [&'static [u8]; 4] = [b"first source, ", b"", b"second, ", b"third"];
+        let readers = sources.map(|source| Box::new(source) as Box<dyn Read>);
+        let mut buffer = Vec::new();
+        let offsets = Encoder::compress_from_readers(readers, &mut buffer, 8, 3).unwrap();
+        assert_eq!(offsets, vec![0, 14, 14, 22]);
+
+        let mut decoder = Decoder::new(std::io::Cursor::new(buffer)).unwrap();
+        assert_eq!(decoder.read_range(14, 22).unwrap(), b"second, ");
+        assert_eq!(
+            decoder.read_range(0, decoder.size()).unwrap(),
+            sources.concat()
+        );
+    }
+
+    #[test]
+    fn test_append_with_validation() {
+        let path = std::env::temp_dir().join(format!(
+            "seekable-zstd-append-validation-{}.szst",
+            std::process::id()
+        ));
+        let mut encoder = Encoder::create(&path, 16, 3).unwrap();
+        encoder.write_all(b"Hello World, ").unwrap();
+        encoder.finish().unwrap();
+
+        for validation in [
+            AppendValidation::SeekTableOnly,
+            AppendValidation::FullVerify,
+        ] {
+            let mut encoder = Encoder::append_with_validation(&path, 16, 3, validation).unwrap();
+            encoder.write_all(b"more").unwrap();
+            encoder.finish_and_verify().unwrap();
+        }
+
+        // Settings the encoder rejects fail before the seek table is cut off.
+        // zstd clamps out-of-range levels, so an oversized frame size it is
+        let original = std::fs::read(&path).unwrap();
+        let result = Encoder::append_with_validation(&path, usize::MAX, 3, AppendValidation::None);
+        assert!(matches!(result, Err(Error::Format(_))));
+        assert_eq!(std::fs::read(&path).unwrap(), original);
+        let mut decoder = crate::decoder::Decoder::open(&path).unwrap();
+        assert_eq!(decoder.read_range(0, 21).unwrap(), b"Hello World, moremore");
+        drop(decoder);
+
+        // Give the first block the reserved block type; the seek table and
+        // frame header still check out
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[6] |= 0x06;
+        std::fs::write(&path, &bytes).unwrap();
+
+        Encoder::append_with_validation(&path, 16, 3, AppendValidation::SeekTableOnly)
+            .unwrap()
+            .finish()
+            .unwrap();
+        let result = Encoder::append_with_validation(&path, 16, 3, AppendValidation::FullVerify);
+        assert!(matches!(result, Err(Error::Format(_))));
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+
+        // Break the first frame's magic number
+        bytes[0] ^= 0xFF;
+        std::fs::write(&path, &bytes).unwrap();
+        let result = Encoder::append_with_validation(&path, 16, 3, AppendValidation::SeekTableOnly);
+        assert!(matches!(result, Err(Error::Format(_))));
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_split_and_compress() {
+        use crate::decoder::Decoder;
+
+        let dir = std::env::temp_dir().join(format!("seekable-zstd-split-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let data: Vec<u8> = (0..2500u32).map(|i| (i % 251) as u8).collect();
+        let paths = Encoder::split_and_compress(data.as_slice(), &dir, 1000, 256, 3).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(paths[2].ends_with("chunk_000002.szst"));
+
+        let mut restored = Vec::new();
+        for path in &paths {
+            restored.extend(Decoder::open(path).unwrap().into_vec().unwrap());
+        }
+        assert_eq!(restored, data);
+
+        let empty = Encoder::split_and_compress(&[][..], &dir, 1000, 256, 3).unwrap();
+        assert!(empty.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_finish_and_verify() {
+        let path =
+            std::env::temp_dir().join(format!("seekable-zstd-verify-{}.szst", std::process::id()));
+        let data: Vec<u8> = (0..5000u32).map(|i| (i % 13) as u8).collect();
+
+        let mut encoder = Encoder::create(&path, 1024, 3).unwrap();
+        encoder.write_all(&data).unwrap();
+        let written = encoder.finish_and_verify().unwrap();
+        assert_eq!(written, std::fs::metadata(&path).unwrap().len());
+
+        let mut encoder = Encoder::append(&path, 1024, 3).unwrap();
+        encoder.write_all(b"more data").unwrap();
+        encoder.finish_and_verify().unwrap();
+
+        let encoder = Encoder::new(Vec::new()).unwrap();
+        assert!(matches!(encoder.finish_and_verify(), Err(Error::Format(_))));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_dry_run_matches_encode() {
+        let data: Vec<u8> = (0..5000u32).map(|i| (i % 13) as u8).collect();
+
+        let report = Encoder::dry_run(&data, 1024, 3).unwrap();
+
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::new_with_options(&mut buffer, 1024, 3).unwrap();
+        encoder.write_all(&data).unwrap();
+        let written = encoder.finish().unwrap();
+
+        assert_eq!(report.frame_count, 5);
+        assert_eq!(report.estimated_compressed_size, written);
+        assert!(report.estimated_compression_ratio > 1.0);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_compress_from_async_read() {
+        use crate::decoder::Decoder;
+        use std::io::Cursor;
+
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 97) as u8).collect();
+        let output = tempfile_path("async_read");
+        let file = std::fs::File::create(&output).unwrap();
+
+        let written = Encoder::compress_from_async_read(data.as_slice(), file, 1024, 3)
+            .await
+            .unwrap();
+
+        let compressed = std::fs::read(&output).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        assert_eq!(written, compressed.len() as u64);
+
+        let mut decoder = Decoder::new(Cursor::new(compressed)).unwrap();
+        assert_eq!(decoder.frame_count(), 10);
+        assert_eq!(decoder.read_range(0, decoder.size()).unwrap(), data);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_compress_async_stream() {
+        use crate::decoder::Decoder;
+        use std::io::Cursor;
+
+        struct Chunks(std::vec::IntoIter<bytes::Bytes>);
+
+        impl futures_core::Stream for Chunks {
+            type Item = bytes::Bytes;
+
+            fn poll_next(
+                mut self: std::pin::Pin<&mut Self>,
+                _cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<Self::Item>> {
+                std::task::Poll::Ready(self.0.next())
+            }
+        }
+
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 97) as u8).collect();
+        let chunks: Vec<bytes::Bytes> = data
+            .chunks(700)
+            .map(bytes::Bytes::copy_from_slice)
+            .collect();
+        let output = tempfile_path("async_stream");
+        let file = std::fs::File::create(&output).unwrap();
+
+        let written = Encoder::compress_async_stream(Chunks(chunks.into_iter()), file, 1024, 3)
+            .await
+            .unwrap();
+
+        let compressed = std::fs::read(&output).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        assert_eq!(written, compressed.len() as u64);
+
+        let mut decoder = Decoder::new(Cursor::new(compressed)).unwrap();
+        assert_eq!(decoder.frame_count(), 10);
+        assert_eq!(decoder.read_range(0, decoder.size()).unwrap(), data);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_compress_from_broadcast() {
+        use crate::decoder::Decoder;
+        use std::io::Cursor;
+
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 97) as u8).collect();
+        let output = tempfile_path("broadcast");
+        let file = std::fs::File::create(&output).unwrap();
+
+        let (sender, receiver) = tokio::sync::broadcast::channel(16);
+        let producer = sender.clone();
+        let compress = tokio::spawn(Encoder::compress_from_broadcast(receiver, file, 1024, 3));
+        for (i, chunk) in data.chunks(1000).enumerate() {
+            let sender = if i % 2 == 0 { &sender } else { &producer };
+            sender.send(bytes::Bytes::copy_from_slice(chunk)).unwrap();
+        }
+        drop(sender);
+        drop(producer);
+        let written = compress.await.unwrap().unwrap();
+
+        let compressed = std::fs::read(&output).unwrap();
+        assert_eq!(written, compressed.len() as u64);
+        let mut decoder = Decoder::new(Cursor::new(compressed)).unwrap();
+        assert_eq!(decoder.read_range(0, decoder.size()).unwrap(), data);
+
+        // A receiver that falls behind reports the lost chunks
+        let (sender, receiver) = tokio::sync::broadcast::channel(1);
+        sender.send(bytes::Bytes::from_static(b"lost")).unwrap();
+        sender.send(bytes::Bytes::from_static(b"kept")).unwrap();
+        drop(sender);
+        let file = std::fs::File::create(&output).unwrap();
+        assert!(Encoder::compress_from_broadcast(receiver, file, 1024, 3)
+            .await
+            .is_err());
+        std::fs::remove_file(&output).unwrap();
+    }
+
+    #[cfg(feature = "tokio")]
+    fn tempfile_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("seekable-zstd-{name}-{}.szst", std::process::id()))
+    }
 }
@@ -11,6 +11,87 @@ pub enum Error {
 
     #[error("Seekable format error: {0}")]
     Format(String),
+
+    /// A read or seek started past the end of the decompressed data.
+    #[error("Offset {offset} is out of bounds for archive size {size}")]
+    OutOfBounds { offset: u64, size: u64 },
+}
+
+impl Error {
+    /// Returns the underlying `io::Error` if this is an `Error::Io`.
+    ///
+    /// Unlike `std::error::Error::source`, this returns a typed reference, so
+    /// callers can inspect `io::ErrorKind` without downcasting.
+    #[must_use]
+    pub fn source_io(&self) -> Option<&io::Error> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of this error for handing to several callers.
+    ///
+    /// `io::Error` is not `Clone`, so an `Error::Io` copy keeps the kind and
+    /// message but not the original source.
+    pub(crate) fn duplicate(&self) -> Error {
+        match self {
+            Error::Io(err) => Error::Io(io::Error::new(err.kind(), err.to_string())),
+            Error::Zstd(msg) => Error::Zstd(msg.clone()),
+            Error::Format(msg) => Error::Format(msg.clone()),
+            Error::OutOfBounds { offset, size } => Error::OutOfBounds {
+                offset: *offset,
+                size: *size,
+            },
+        }
+    }
+
+    /// Formats this error followed by every `source()` cause.
+    ///
+    /// Produces `"A: caused by B: caused by C"`, like `anyhow`'s chain
+    /// formatting, for log lines and language bindings that only carry a
+    /// message string. A cause whose message is already part of the previous
+    /// one, such as the `io::Error` wrapped by `Error::Io`, is skipped.
+    #[must_use]
+    pub fn display_chain(&self) -> String {
+        let mut chain = self.to_string();
+        let mut previous = chain.clone();
+        let mut source = std::error::Error::source(self);
+        while let Some(cause) = source {
+            let message = cause.to_string();
+            if !previous.contains(&message) {
+                chain.push_str(": caused by ");
+                chain.push_str(&message);
+            }
+            previous = message;
+            source = cause.source();
+        }
+        chain
+    }
+
+    /// Converts this error into an `io::Error`.
+    ///
+    /// `Error::Io` is unwrapped unchanged; zstd and format errors become
+    /// `io::ErrorKind::InvalidData` with the original message, and
+    /// out-of-bounds offsets become `io::ErrorKind::InvalidInput`.
+    #[must_use]
+    pub fn into_io_error(self) -> io::Error {
+        match self {
+            Error::Io(err) => err,
+            Error::Zstd(msg) | Error::Format(msg) => {
+                io::Error::new(io::ErrorKind::InvalidData, msg)
+            }
+            err @ Error::OutOfBounds { .. } => {
+                io::Error::new(io::ErrorKind::InvalidInput, err.to_string())
+            }
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        err.into_io_error()
+    }
 }
 
 // Convert zeekstd error to our Error
@@ -19,3 +100,62 @@ impl From<zeekstd::Error> for Error {
         Error::Zstd(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_io() {
+        let err = Error::from(io::Error::new(io::ErrorKind::WouldBlock, "busy"));
+        assert_eq!(
+            err.source_io().map(io::Error::kind),
+            Some(io::ErrorKind::WouldBlock)
+        );
+
+        let err = Error::Format("bad".to_string());
+        assert!(err.source_io().is_none());
+    }
+
+    #[test]
+    fn test_display_chain() {
+        #[derive(Error, Debug)]
+        #[error("disk unplugged")]
+        struct Inner;
+        #[derive(Error, Debug)]
+        #[error("read failed")]
+        struct Outer(#[source] Inner);
+
+        let err = Error::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+        assert_eq!(err.display_chain(), "IO error: missing");
+
+        let err = Error::from(io::Error::other(Outer(Inner)));
+        assert_eq!(
+            err.display_chain(),
+            "IO error: read failed: caused by disk unplugged"
+        );
+
+        let err = Error::Format("bad".to_string());
+        assert_eq!(err.display_chain(), "Seekable format error: bad");
+    }
+
+    #[test]
+    fn test_into_io_error() {
+        let err = Error::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+        assert_eq!(err.into_io_error().kind(), io::ErrorKind::NotFound);
+
+        let err = Error::Zstd("corrupted".to_string()).into_io_error();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "corrupted");
+
+        let err = Error::Format("bad seek table".to_string()).into_io_error();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = Error::OutOfBounds { offset: 7, size: 5 }.into_io_error();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "Offset 7 is out of bounds for archive size 5"
+        );
+    }
+}
@@ -162,6 +162,50 @@ pub unsafe extern "C" fn seekable_read_range(
     0 // Success
 }
 
+/// Decompresses a range of bytes and writes it to a file descriptor.
+///
+/// The range is written in chunks of at most one frame, so arbitrarily large
+/// ranges can be streamed to a file, pipe, or socket. The descriptor is not
+/// closed. Returns 0 on success. Unix only; the header declares it when
+/// `SEEKABLE_ZSTD_UNIX` is defined.
+///
+/// # Safety
+/// `decoder` must be a valid pointer returned by `seekable_open`.
+/// `fd` must be an open file descriptor that is writable for the duration
+/// of the call.
+#[cfg(unix)]
+#[no_mangle]
+pub unsafe extern "C" fn seekable_read_range_to_fd(
+    decoder: *mut SeekableDecoder,
+    start: u64,
+    end: u64,
+    fd: std::os::raw::c_int,
+) -> i32 {
+    use std::mem::ManuallyDrop;
+    use std::os::fd::FromRawFd;
+
+    if decoder.is_null() {
+        set_error(&"Decoder pointer is null");
+        return -1;
+    }
+    if fd < 0 {
+        set_error(&"Invalid file descriptor");
+        return -1;
+    }
+
+    let decoder = unsafe { &mut *decoder };
+    // ManuallyDrop keeps the caller's descriptor open
+    let mut file = ManuallyDrop::new(unsafe { File::from_raw_fd(fd) });
+
+    match decoder.inner.read_range_into_writer(start, end, &mut *file) {
+        Ok(_) => 0,
+        Err(e) => {
+            set_error(&format!("Read error: {e}"));
+            -3
+        }
+    }
+}
+
 /// Reads multiple ranges in parallel.
 ///
 /// # Safety
@@ -275,3 +319,49 @@ pub unsafe extern "C" fn seekable_last_error() -> *const c_char {
         None => ptr::null(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_range_to_fd() {
+        use crate::encoder::Encoder;
+        use std::io::Read;
+        use std::os::fd::FromRawFd;
+
+        let path =
+            std::env::temp_dir().join(format!("seekable-zstd-ffi-{}.szst", std::process::id()));
+        let mut encoder = Encoder::with_frame_size(File::create(&path).unwrap(), 4).unwrap();
+        encoder.write_all(b"aaaabbbbccccdd").unwrap();
+        encoder.finish().unwrap();
+
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+        let decoder = unsafe { seekable_open(c_path.as_ptr()) };
+        assert!(!decoder.is_null());
+
+        let mut fds = [0; 2];
+        // SAFETY: `fds` has room for both descriptors
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        let [read_fd, write_fd] = fds;
+        assert_eq!(
+            unsafe { seekable_read_range_to_fd(decoder, 2, 13, write_fd) },
+            0
+        );
+        assert_eq!(unsafe { seekable_read_range_to_fd(decoder, 0, 1, -1) }, -1);
+
+        // The descriptor is still open, and the pipe holds exactly the range
+        // SAFETY: both descriptors came from `pipe` and are owned here
+        assert_ne!(unsafe { libc::fcntl(write_fd, libc::F_GETFD) }, -1);
+        drop(unsafe { File::from_raw_fd(write_fd) });
+        let mut output = Vec::new();
+        unsafe { File::from_raw_fd(read_fd) }
+            .read_to_end(&mut output)
+            .unwrap();
+        assert_eq!(output, b"aabbbbccccd");
+
+        unsafe { seekable_close(decoder) };
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+use std::io::{self, Write};
+
+/// Hash functions available to [`Decoder::content_hash`].
+///
+/// Each variant is enabled by the crate feature of the same family:
+/// `sha2` for `Sha256` and `Sha512`, `blake3` for `Blake3`, and `xxhash` for
+/// `Xxh3`.
+///
+/// [`Decoder::content_hash`]: crate::Decoder::content_hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HashAlgorithm {
+    #[cfg(feature = "sha2")]
+    Sha256,
+    #[cfg(feature = "sha2")]
+    Sha512,
+    #[cfg(feature = "blake3")]
+    Blake3,
+    /// 64-bit XXH3; the digest is returned big-endian.
+    #[cfg(feature = "xxhash")]
+    Xxh3,
+}
+
+/// Incremental hasher that accepts decompressed data through `Write`.
+pub(crate) enum Hasher {
+    #[cfg(feature = "sha2")]
+    Sha256(sha2::Sha256),
+    #[cfg(feature = "sha2")]
+    Sha512(sha2::Sha512),
+    #[cfg(feature = "blake3")]
+    Blake3(Box<blake3::Hasher>),
+    #[cfg(feature = "xxhash")]
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+}
+
+impl Hasher {
+    pub(crate) fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            #[cfg(feature = "sha2")]
+            HashAlgorithm::Sha256 => Hasher::Sha256(sha2::Digest::new()),
+            #[cfg(feature = "sha2")]
+            HashAlgorithm::Sha512 => Hasher::Sha512(sha2::Digest::new()),
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => Hasher::Blake3(Box::default()),
+            #[cfg(feature = "xxhash")]
+            HashAlgorithm::Xxh3 => Hasher::Xxh3(Box::default()),
+        }
+    }
+
+    pub(crate) fn finalize(self) -> Vec<u8> {
+        match self {
+            #[cfg(feature = "sha2")]
+            Hasher::Sha256(hasher) => sha2::Digest::finalize(hasher).to_vec(),
+            #[cfg(feature = "sha2")]
+            Hasher::Sha512(hasher) => sha2::Digest::finalize(hasher).to_vec(),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+            #[cfg(feature = "xxhash")]
+            Hasher::Xxh3(hasher) => hasher.digest().to_be_bytes().to_vec(),
+        }
+    }
+}
+
+impl Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(feature = "sha2")]
+            Hasher::Sha256(hasher) => sha2::Digest::update(hasher, buf),
+            #[cfg(feature = "sha2")]
+            Hasher::Sha512(hasher) => sha2::Digest::update(hasher, buf),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => {
+                hasher.update(buf);
+            }
+            #[cfg(feature = "xxhash")]
+            Hasher::Xxh3(hasher) => hasher.update(buf),
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
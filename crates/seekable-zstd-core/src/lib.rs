@@ -2,15 +2,39 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod atomic;
 pub mod decoder;
+pub mod dedup;
 pub mod encoder;
 pub mod error;
 pub mod ffi;
+#[cfg(any(feature = "sha2", feature = "blake3", feature = "xxhash"))]
+pub mod hash;
+#[cfg(unix)]
+pub mod lock;
+pub mod metadata;
 pub mod parallel;
+pub mod priority;
+pub mod seek_table;
+pub mod typed;
 
-pub use decoder::Decoder;
-pub use encoder::Encoder;
+pub use atomic::AtomicEncoder;
+#[cfg(feature = "xxhash")]
+pub use decoder::FrameChecksum;
+pub use decoder::{
+    Decoder, DecoderStats, DecoderStream, LazyDecompressedRange, ReadRangeResult, RevFrameIter,
+};
+pub use dedup::DeduplicatingDecoder;
+pub use encoder::{AppendValidation, DryRunReport, Encoder};
 pub use error::Error;
-pub use parallel::ParallelDecoder;
+#[cfg(any(feature = "sha2", feature = "blake3", feature = "xxhash"))]
+pub use hash::HashAlgorithm;
+#[cfg(unix)]
+pub use lock::FileLock;
+pub use metadata::ArchiveMetadata;
+pub use parallel::{ParallelDecoder, ReadSeek, ReadStrategy, SelfParallelDecoder};
+pub use priority::PriorityParallelDecoder;
+pub use seek_table::{FrameInfo, SeekTable};
+pub use typed::TypedDecoder;
 
 pub type Result<T> = std::result::Result<T, Error>;
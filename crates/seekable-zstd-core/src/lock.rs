@@ -0,0 +1,125 @@
+use std::fs::File;
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+/// An advisory `flock` on an archive file, released on drop.
+///
+/// Returned by [`Decoder::open_with_lock`] (shared) and
+/// [`Encoder::open_with_exclusive_lock`] (exclusive). A shared lock blocks
+/// exclusive lockers and vice versa, so a reader holding one never sees an
+/// appending writer replace the seek table mid-read. Locks are advisory:
+/// only processes that also take them are kept out.
+///
+/// [`Decoder::open_with_lock`]: crate::Decoder::open_with_lock
+/// [`Encoder::open_with_exclusive_lock`]: crate::Encoder::open_with_exclusive_lock
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Blocks until a shared lock on `file` is acquired.
+    pub(crate) fn shared(file: &File) -> io::Result<Self> {
+        Self::acquire(file, libc::LOCK_SH)
+    }
+
+    /// Blocks until an exclusive lock on `file` is acquired.
+    pub(crate) fn exclusive(file: &File) -> io::Result<Self> {
+        Self::acquire(file, libc::LOCK_EX)
+    }
+
+    fn acquire(file: &File, operation: libc::c_int) -> io::Result<Self> {
+        // The duplicate shares the lock, so it stays held after the
+        // caller's handle is closed
+        let file = file.try_clone()?;
+        loop {
+            // SAFETY: the descriptor is owned by `file` and open for the call
+            if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+                return Ok(Self { file });
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // SAFETY: the descriptor is owned by `self.file` and still open.
+        // Closing it would release the lock too; unlocking first releases it
+        // even if another duplicate of the descriptor is still open.
+        unsafe {
+            libc::flock(self.file.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::encoder::Encoder;
+
+    fn try_lock(file: &File, operation: libc::c_int) -> bool {
+        // SAFETY: the descriptor is owned by `file` and open for the call
+        let locked = unsafe { libc::flock(file.as_raw_fd(), operation | libc::LOCK_NB) } == 0;
+        if locked {
+            // SAFETY: as above
+            unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_UN) };
+        }
+        locked
+    }
+
+    #[test]
+    fn test_shared_and_exclusive_locks() {
+        let path =
+            std::env::temp_dir().join(format!("seekable-zstd-lock-{}.szst", std::process::id()));
+        std::fs::write(&path, b"").unwrap();
+        let other = File::open(&path).unwrap();
+
+        let shared = FileLock::shared(&File::open(&path).unwrap()).unwrap();
+        assert!(try_lock(&other, libc::LOCK_SH));
+        assert!(!try_lock(&other, libc::LOCK_EX));
+        drop(shared);
+
+        let exclusive = FileLock::exclusive(&File::open(&path).unwrap()).unwrap();
+        assert!(!try_lock(&other, libc::LOCK_SH));
+        drop(exclusive);
+        assert!(try_lock(&other, libc::LOCK_EX));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_locked_read_and_append() {
+        let path = std::env::temp_dir().join(format!(
+            "seekable-zstd-lock-append-{}.szst",
+            std::process::id()
+        ));
+        let mut encoder = Encoder::with_frame_size(File::create(&path).unwrap(), 16).unwrap();
+        encoder.write_all(b"Hello ").unwrap();
+        encoder.finish().unwrap();
+        let other = File::open(&path).unwrap();
+
+        let (mut decoder, lock) = Decoder::open_with_lock(&path).unwrap();
+        assert!(!try_lock(&other, libc::LOCK_EX));
+        assert_eq!(decoder.read_range(0, 6).unwrap(), b"Hello ");
+        drop(decoder);
+        // The lock outlives the decoder's own handle
+        assert!(!try_lock(&other, libc::LOCK_EX));
+        drop(lock);
+
+        let (mut encoder, lock) = Encoder::open_with_exclusive_lock(&path, 16, 3).unwrap();
+        assert!(!try_lock(&other, libc::LOCK_SH));
+        encoder.write_all(b"World").unwrap();
+        encoder.finish().unwrap();
+        drop(lock);
+
+        let (mut decoder, _lock) = Decoder::open_with_lock(&path).unwrap();
+        assert_eq!(decoder.read_range(0, 11).unwrap(), b"Hello World");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
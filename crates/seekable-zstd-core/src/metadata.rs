@@ -0,0 +1,59 @@
+use crate::seek_table::SeekTable;
+use std::fmt;
+
+/// Archive-level information gathered from the seek table, without reading
+/// any frame data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ArchiveMetadata {
+    /// Total decompressed size in bytes.
+    pub size: u64,
+    pub frame_count: u64,
+    /// Combined compressed size of all frames, excluding the seek table.
+    pub compressed_size: u64,
+    /// Size of the serialized seek table in bytes.
+    pub seek_table_size: u64,
+    /// Largest decompressed frame size in bytes.
+    pub max_frame_size: u64,
+    /// Decompressed size divided by compressed frame size, or `0.0` for an
+    /// empty archive.
+    pub compression_ratio: f64,
+}
+
+impl ArchiveMetadata {
+    pub(crate) fn from_seek_table(seek_table: &SeekTable) -> Self {
+        let size = seek_table.size();
+        let compressed_size = seek_table.compressed_size();
+
+        #[allow(clippy::cast_precision_loss)]
+        let compression_ratio = if compressed_size == 0 {
+            0.0
+        } else {
+            size as f64 / compressed_size as f64
+        };
+
+        Self {
+            size,
+            frame_count: seek_table.frame_count(),
+            compressed_size,
+            seek_table_size: seek_table.inner.clone().into_serializer().encoded_len() as u64,
+            max_frame_size: seek_table.inner.max_frame_size_decomp(),
+            compression_ratio,
+        }
+    }
+}
+
+impl fmt::Display for ArchiveMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes in {} frames ({} bytes compressed, {} byte seek table, ratio {:.2}, largest frame {} bytes)",
+            self.size,
+            self.frame_count,
+            self.compressed_size,
+            self.seek_table_size,
+            self.compression_ratio,
+            self.max_frame_size
+        )
+    }
+}
@@ -1,14 +1,299 @@
-use crate::decoder::Decoder;
+use crate::decoder::{check_in_bounds, Decoder};
 use crate::error::Error;
+use crate::metadata::ArchiveMetadata;
+use crate::seek_table::{FrameInfo, SeekTable};
 use rayon::prelude::*;
 use std::fs::File;
+use std::io::{Cursor, Read, Seek};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, OnceLock, PoisonError};
 
+/// A reader that can be stored as a trait object in a reader pool.
+///
+/// Implemented for every `Read + Seek` type.
+pub trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek + ?Sized> ReadSeek for T {}
+
+/// A decoder over any of the readers a `Source` hands out.
+type SourceDecoder<'r> = Decoder<'static, Box<dyn ReadSeek + Send + 'r>>;
+
+fn boxed<'r>(reader: impl ReadSeek + Send + 'r) -> Box<dyn ReadSeek + Send + 'r> {
+    Box::new(reader)
+}
+
+/// Where a `ParallelDecoder` reads its compressed data from.
 #[derive(Clone)]
-pub struct ParallelDecoder {
+enum Source {
+    Path(PathBuf),
+    Bytes(Arc<Vec<u8>>),
+    Pool(Arc<ReaderPool>),
+    Warm(Arc<WarmCache>),
+    Handles(Arc<DecoderPool>),
+}
+
+impl Source {
+    /// Runs `f` on a decoder over the source: a fresh one, or one kept
+    /// open by a `DecoderPool`.
+    fn with_decoder<T>(
+        &self,
+        f: impl FnOnce(&mut SourceDecoder<'_>) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        match self {
+            Source::Path(path) => f(&mut Decoder::new(boxed(File::open(path)?))?),
+            Source::Bytes(data) => f(&mut Decoder::new(boxed(Cursor::new(data.as_slice())))?),
+            Source::Pool(pool) => {
+                let mut reader = pool.check_out();
+                // `from_reader_pool` checked every reader against the table
+                let mut decoder = Decoder::from_reader_and_trusted_seek_table(
+                    boxed(reader.get()),
+                    &pool.seek_table,
+                )?;
+                f(&mut decoder)
+            }
+            Source::Warm(cache) => cache.source.with_decoder(f),
+            Source::Handles(pool) => pool.with_decoder(f),
+        }
+    }
+
+    /// Opens a fresh decoder over the source and reads a single range.
+    fn read_range(&self, start: u64, end: u64) -> Result<Vec<u8>, Error> {
+        match self {
+            Source::Warm(cache) => cache.read_range(start, end),
+            _ => self.with_decoder(|decoder| decoder.read_range(start, end)),
+        }
+    }
+
+    /// Opens a fresh decoder over the source and reads a single frame.
+    fn read_frame(&self, frame_index: u64) -> Result<Vec<u8>, Error> {
+        match self {
+            Source::Warm(cache) => cache.read_frame(frame_index),
+            _ => self.with_decoder(|decoder| decoder.read_frame(frame_index)),
+        }
+    }
+
+    /// Opens a fresh decoder over the source and streams a range into a
+    /// channel, one frame at a time. Blocks; see
+    /// [`Decoder::read_range_into_channel`].
+    #[cfg(feature = "tokio")]
+    fn read_range_into_channel(
+        &self,
+        start: u64,
+        end: u64,
+        sender: tokio::sync::mpsc::Sender<Vec<u8>>,
+    ) -> Result<(), Error> {
+        self.with_decoder(|decoder| decoder.read_range_into_channel(start, end, sender))
+    }
+
+    fn seek_table(&self) -> Result<SeekTable, Error> {
+        match self {
+            Source::Path(path) => SeekTable::from_reader(&mut File::open(path)?),
+            Source::Bytes(data) => SeekTable::from_reader(&mut Cursor::new(data.as_slice())),
+            Source::Pool(pool) => Ok(pool.seek_table.clone()),
+            Source::Warm(cache) => Ok(cache.seek_table.clone()),
+            Source::Handles(pool) => Ok(pool.seek_table.clone()),
+        }
+    }
+}
+
+/// Decoders kept open on one archive, one slot per rayon worker.
+///
+/// Each slot opens the file on first use and keeps its decoder, so a
+/// worker pays for the open once rather than once per range. The seek table
+/// is parsed once for all of them.
+struct DecoderPool {
     path: PathBuf,
-    size: u64,
-    frame_count: u64,
+    seek_table: SeekTable,
+    slots: Vec<Mutex<Option<SourceDecoder<'static>>>>,
+}
+
+impl DecoderPool {
+    fn with_decoder<T>(
+        &self,
+        f: impl FnOnce(&mut SourceDecoder<'_>) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        // Threads outside the pool the slots were sized for share slots,
+        // and wait for each other on the lock
+        let index = rayon::current_thread_index().unwrap_or(0) % self.slots.len();
+        let mut slot = self.slots[index]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let decoder = match &mut *slot {
+            Some(decoder) => decoder,
+            // The table was read from this same path
+            slot @ None => slot.insert(Decoder::from_reader_and_trusted_seek_table(
+                boxed(File::open(&self.path)?),
+                &self.seek_table,
+            )?),
+        };
+        f(decoder)
+    }
+}
+
+/// An archive decompressed in full when a `ParallelDecoder` is created with
+/// `with_warm_cache`, so that range and frame reads are plain copies.
+struct WarmCache {
+    data: Vec<u8>,
+    seek_table: SeekTable,
+    /// The compressed archive, for reads that stream through a decoder.
+    source: Source,
+}
+
+impl WarmCache {
+    fn read_range(&self, start: u64, end: u64) -> Result<Vec<u8>, Error> {
+        if end < start {
+            return Err(Error::Format(
+                "End offset cannot be less than start offset".to_string(),
+            ));
+        }
+        check_in_bounds(start, end, self.data.len() as u64)?;
+        let start = usize::try_from(start).unwrap_or(usize::MAX);
+        let end = usize::try_from(end)
+            .unwrap_or(usize::MAX)
+            .min(self.data.len());
+        if start >= end {
+            return Ok(Vec::new());
+        }
+        Ok(self.data[start..end].to_vec())
+    }
+
+    fn read_frame(&self, frame_index: u64) -> Result<Vec<u8>, Error> {
+        let range = self
+            .seek_table
+            .frame_range(frame_index)
+            .ok_or_else(|| Error::Format(format!("Frame {frame_index} out of range")))?;
+        self.read_range(range.start, range.end)
+    }
+}
+
+/// Pre-opened readers shared by the workers of a `ParallelDecoder`.
+struct ReaderPool {
+    readers: Mutex<Vec<Box<dyn ReadSeek + Send>>>,
+    returned: Condvar,
+    /// Parsed once at construction, so checkouts skip reading the footer.
+    seek_table: SeekTable,
+}
+
+impl ReaderPool {
+    /// Takes a reader from the pool, blocking until one is free.
+    fn check_out(&self) -> PooledReader<'_> {
+        let mut readers = self.readers.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(reader) = readers.pop() {
+                return PooledReader {
+                    pool: self,
+                    reader: Some(reader),
+                };
+            }
+            readers = self
+                .returned
+                .wait(readers)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+/// A reader checked out of a `ReaderPool`, returned to it on drop.
+struct PooledReader<'p> {
+    pool: &'p ReaderPool,
+    reader: Option<Box<dyn ReadSeek + Send>>,
+}
+
+impl PooledReader<'_> {
+    fn get(&mut self) -> &mut (dyn ReadSeek + Send) {
+        self.reader
+            .as_deref_mut()
+            .expect("reader is only taken on drop")
+    }
+}
+
+impl Drop for PooledReader<'_> {
+    fn drop(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            self.pool
+                .readers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(reader);
+            self.pool.returned.notify_one();
+        }
+    }
+}
+
+/// Counting semaphore bounding how many workers touch the source at once.
+struct IoLimit {
+    available: Mutex<usize>,
+    released: Condvar,
+}
+
+impl IoLimit {
+    fn new(permits: usize) -> Self {
+        Self {
+            available: Mutex::new(permits),
+            released: Condvar::new(),
+        }
+    }
+
+    fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let mut available = self
+            .available
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        while *available == 0 {
+            available = self
+                .released
+                .wait(available)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        *available -= 1;
+        drop(available);
+
+        let _permit = Permit { limit: self };
+        f()
+    }
+}
+
+/// Returns an `IoLimit` permit on drop, so a panicking read still frees it.
+struct Permit<'a> {
+    limit: &'a IoLimit,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        *self
+            .limit
+            .available
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) += 1;
+        self.limit.released.notify_one();
+    }
+}
+
+/// Total bytes below which [`ReadStrategy::Adaptive`] reads sequentially.
+const ADAPTIVE_PARALLEL_THRESHOLD: u64 = 1024 * 1024;
+
+/// How [`ParallelDecoder::read_ranges_with_strategy`] spreads work across
+/// threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadStrategy {
+    /// Runs on rayon's global thread pool, like `read_ranges`.
+    GlobalRayon,
+    /// Builds a pool with this many threads for the call and drops it
+    /// afterwards. A count of `0` lets rayon choose.
+    LocalThreadPool(usize),
+    /// Reads the ranges one after another on the calling thread.
+    Sequential,
+    /// Reads sequentially when there is a single range or less than 1 MiB in
+    /// total, where thread handoff costs more than it saves, and on the
+    /// global pool otherwise.
+    Adaptive,
+}
+
+#[derive(Clone)]
+pub struct ParallelDecoder {
+    source: Source,
+    /// Filled at construction, or on first use for [`ParallelDecoder::open_lazy`].
+    metadata: OnceLock<ArchiveMetadata>,
 }
 
 impl ParallelDecoder {
@@ -18,25 +303,232 @@ impl ParallelDecoder {
     ///
     /// Returns an error if the file cannot be opened or if the decoder cannot be initialized.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
-        let path_buf = path.as_ref().to_path_buf();
-        let file = File::open(&path_buf)?;
-        let decoder = Decoder::new(file)?;
+        let decoder = Self::open_lazy(path);
+        decoder.load_metadata()?;
+        Ok(decoder)
+    }
 
-        Ok(Self {
-            path: path_buf,
-            size: decoder.size(),
-            frame_count: decoder.frame_count(),
-        })
+    /// Creates a parallel decoder for the given file path without touching
+    /// the file.
+    ///
+    /// The seek table is read on first use: the first call to
+    /// [`size`](Self::size), [`frame_count`](Self::frame_count),
+    /// [`metadata`](Self::metadata), or [`read_ranges`](Self::read_ranges).
+    /// Useful for holding many decoders of which only a few are ever read,
+    /// such as a cache with cold entries. A missing or invalid file is only
+    /// reported at that point.
+    #[must_use]
+    pub fn open_lazy<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            source: Source::Path(path.as_ref().to_path_buf()),
+            metadata: OnceLock::new(),
+        }
+    }
+
+    /// Opens an archive and decompresses all of it into memory up front.
+    ///
+    /// Every frame is decoded in parallel on the global rayon pool before
+    /// this returns. Afterwards [`read_ranges`](Self::read_ranges) and the
+    /// other range and frame reads copy from memory without touching the
+    /// file or decompressing anything; only `read_range_async_write` (with the
+    /// `tokio` feature) still streams from the file. This trades startup time
+    /// and memory for the fastest possible reads, and suits small archives
+    /// that are read over and over, such as shared configuration data.
+    ///
+    /// `cache_capacity` is the most decompressed bytes the caller is willing
+    /// to hold. The whole archive is cached, so nothing is ever evicted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Format` if the archive decompresses to more than
+    /// `cache_capacity` bytes, or an error if the file cannot be opened or a
+    /// frame cannot be decompressed.
+    pub fn with_warm_cache<P: AsRef<Path>>(path: P, cache_capacity: usize) -> Result<Self, Error> {
+        let source = Source::Path(path.as_ref().to_path_buf());
+        let seek_table = source.seek_table()?;
+        let size = seek_table.size();
+        if size > cache_capacity as u64 {
+            return Err(Error::Format(format!(
+                "Archive decompresses to {size} bytes, more than the cache capacity of {cache_capacity} bytes"
+            )));
+        }
+
+        let frames: Vec<Vec<u8>> = (0..seek_table.frame_count())
+            .into_par_iter()
+            .map(|index| source.read_frame(index))
+            .collect::<Result<_, _>>()?;
+        let cache = WarmCache {
+            data: frames.concat(),
+            seek_table: seek_table.clone(),
+            source,
+        };
+        Ok(Self::with_seek_table(
+            Source::Warm(Arc::new(cache)),
+            &seek_table,
+        ))
     }
 
+    /// Opens a parallel decoder that keeps one open decoder per worker
+    /// thread.
+    ///
+    /// [`open`](Self::open) opens the file and parses the seek table again
+    /// for every range it reads. Here the seek table is parsed once, at
+    /// construction, and each rayon worker opens the file on its first read and keeps
+    /// that decoder for later ones, so archives read in many small ranges
+    /// skip an open and a seek table parse per range. Workers also benefit
+    /// from [`Decoder::read_range`] reusing recently decoded frames.
+    ///
+    /// Decoders are kept for one slot per thread of the current rayon pool;
+    /// reads from other threads share slots and take turns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or is not a valid
+    /// seekable archive.
+    pub fn with_pool<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let seek_table = SeekTable::from_reader(&mut File::open(&path)?)?;
+        let slots = (0..rayon::current_num_threads())
+            .map(|_| Mutex::new(None))
+            .collect();
+
+        Ok(Self::with_seek_table(
+            Source::Handles(Arc::new(DecoderPool {
+                path,
+                seek_table: seek_table.clone(),
+                slots,
+            })),
+            &seek_table,
+        ))
+    }
+
+    /// Returns whether the seek table has been read yet.
+    ///
+    /// Always `true` except for a decoder from
+    /// [`open_lazy`](Self::open_lazy) that has not been used.
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        self.metadata.get().is_some()
+    }
+
+    /// Returns the cached metadata, reading the seek table if needed.
+    ///
+    /// Failures are not cached, so a later call tries again.
+    fn load_metadata(&self) -> Result<&ArchiveMetadata, Error> {
+        if let Some(metadata) = self.metadata.get() {
+            return Ok(metadata);
+        }
+        let metadata = ArchiveMetadata::from_seek_table(&self.source.seek_table()?);
+        Ok(self.metadata.get_or_init(|| metadata))
+    }
+
+    fn with_seek_table(source: Source, seek_table: &SeekTable) -> Self {
+        Self {
+            source,
+            metadata: OnceLock::from(ArchiveMetadata::from_seek_table(seek_table)),
+        }
+    }
+
+    /// Creates a parallel decoder over an in-memory archive.
+    ///
+    /// Metadata is read eagerly. Each worker decodes from its own cursor over
+    /// the shared buffer, so no file I/O is involved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a valid seekable archive.
+    pub fn from_bytes(data: Arc<Vec<u8>>) -> Result<Self, Error> {
+        let seek_table = SeekTable::from_reader(&mut Cursor::new(data.as_slice()))?;
+        Ok(Self::with_seek_table(Source::Bytes(data), &seek_table))
+    }
+
+    /// Creates a parallel decoder that reads through a pool of pre-opened
+    /// readers over the same archive.
+    ///
+    /// Useful where opening a file is expensive, such as remote file
+    /// systems with slow lookups. Each read checks a reader out of the pool,
+    /// decodes, and returns it; when every reader is in use, further reads
+    /// block until one is returned, so the pool size also bounds
+    /// concurrency. Every reader's seek table is read here and must match the
+    /// first one's; after that, checkouts reuse the parsed table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Format` if `readers` is empty or a reader holds a
+    /// different archive than the first, or an error if a reader does not
+    /// hold a valid seekable archive.
+    pub fn from_reader_pool(mut readers: Vec<Box<dyn ReadSeek + Send>>) -> Result<Self, Error> {
+        let (first, rest) = readers
+            .split_first_mut()
+            .ok_or_else(|| Error::Format("Reader pool is empty".to_string()))?;
+        let seek_table = SeekTable::from_reader(first)?;
+        for (index, reader) in rest.iter_mut().enumerate() {
+            if !SeekTable::from_reader(reader)?.validate_against(&seek_table) {
+                return Err(Error::Format(format!(
+                    "Reader {} holds a different archive than reader 0",
+                    index + 1
+                )));
+            }
+        }
+
+        Ok(Self::with_seek_table(
+            Source::Pool(Arc::new(ReaderPool {
+                readers: Mutex::new(readers),
+                returned: Condvar::new(),
+                seek_table: seek_table.clone(),
+            })),
+            &seek_table,
+        ))
+    }
+
+    /// Returns the total decompressed size.
+    ///
+    /// For a decoder from [`open_lazy`](Self::open_lazy) this may read the
+    /// seek table, and returns `0` if it cannot be read.
     #[must_use]
     pub fn size(&self) -> u64 {
-        self.size
+        self.load_metadata().map_or(0, |metadata| metadata.size)
     }
 
+    /// Returns the number of frames.
+    ///
+    /// For a decoder from [`open_lazy`](Self::open_lazy) this may read the
+    /// seek table, and returns `0` if it cannot be read.
     #[must_use]
     pub fn frame_count(&self) -> u64 {
-        self.frame_count
+        self.load_metadata()
+            .map_or(0, |metadata| metadata.frame_count)
+    }
+
+    /// Reads the archive's seek table.
+    ///
+    /// The table is not cached; each call reads it from the source again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be read.
+    pub fn seek_table(&self) -> Result<SeekTable, Error> {
+        self.source.seek_table()
+    }
+
+    /// Returns the position of every frame, in order, for planning reads.
+    ///
+    /// See [`SeekTable::frames`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the seek table cannot be read.
+    pub fn frames(&self) -> Result<Vec<FrameInfo>, Error> {
+        Ok(self.seek_table()?.frames())
+    }
+
+    /// Returns size, frame, and seek table information in one struct.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the seek table cannot be read.
+    pub fn metadata(&self) -> Result<ArchiveMetadata, Error> {
+        self.load_metadata().copied()
     }
 
     /// Reads multiple ranges in parallel.
@@ -45,17 +537,740 @@ impl ParallelDecoder {
     ///
     /// Returns an error if any of the reads fail.
     pub fn read_ranges(&self, ranges: &[(u64, u64)]) -> Result<Vec<Vec<u8>>, Error> {
+        self.load_metadata()?;
+
         // Collect results into a Vec<Result<Vec<u8>, Error>> first
         let results: Vec<Result<Vec<u8>, Error>> = ranges
             .par_iter()
-            .map(|(start, end)| {
-                let file = File::open(&self.path)?;
-                let mut decoder = Decoder::new(file)?;
-                decoder.read_range(*start, *end)
-            })
+            .map(|(start, end)| self.source.read_range(*start, *end))
             .collect();
 
         // Then collect into Result<Vec<Vec<u8>>, Error>
         results.into_iter().collect()
     }
+
+    /// Decompresses frames in parallel and transforms each one in place.
+    ///
+    /// Each frame is decoded on a rayon worker and passed straight to
+    /// `transform`, so callers that only need parsed records never hold all
+    /// the raw frame buffers at once. Results are returned in the same order
+    /// as `frame_indices`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a frame index is out of range, if a read fails,
+    /// or the first error returned by `transform`.
+    pub fn read_frames_with_transform<T, F>(
+        &self,
+        frame_indices: &[u64],
+        transform: F,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: Send,
+        F: Fn(&[u8]) -> Result<T, Error> + Sync,
+    {
+        frame_indices
+            .par_iter()
+            .map(|index| transform(&self.source.read_frame(*index)?))
+            .collect()
+    }
+
+    /// Decompresses `start..end` into an async writer without blocking the
+    /// runtime.
+    ///
+    /// Decompression runs on tokio's blocking thread pool and hands over
+    /// one frame at a time; each frame is written to `writer` as it
+    /// arrives, so an HTTP response body can start before the whole range
+    /// is decoded. Returns the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading or decompressing the range fails, or if
+    /// writing to `writer` fails.
+    #[cfg(feature = "tokio")]
+    pub async fn read_range_async_write<W>(
+        &self,
+        start: u64,
+        end: u64,
+        mut writer: W,
+    ) -> Result<u64, Error>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(2);
+        let source = self.source.clone();
+        let decode =
+            tokio::task::spawn_blocking(move || source.read_range_into_channel(start, end, sender));
+
+        let mut written = 0u64;
+        let mut write_result = Ok(());
+        while let Some(chunk) = receiver.recv().await {
+            if let Err(err) = writer.write_all(&chunk).await {
+                write_result = Err(err);
+                break;
+            }
+            written += chunk.len() as u64;
+        }
+        // Dropping the receiver stops the decoder early after a write error
+        drop(receiver);
+
+        let decoded = decode
+            .await
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+        write_result?;
+        decoded?;
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    /// Reads multiple ranges using the given threading strategy.
+    ///
+    /// Results are returned in the same order as `ranges`, whatever the
+    /// strategy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a local thread pool cannot be built, or if any of
+    /// the reads fail.
+    pub fn read_ranges_with_strategy(
+        &self,
+        ranges: &[(u64, u64)],
+        strategy: ReadStrategy,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        match strategy {
+            ReadStrategy::GlobalRayon => self.read_ranges(ranges),
+            ReadStrategy::LocalThreadPool(threads) => {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+                pool.install(|| self.read_ranges(ranges))
+            }
+            ReadStrategy::Sequential => ranges
+                .iter()
+                .map(|(start, end)| self.source.read_range(*start, *end))
+                .collect(),
+            ReadStrategy::Adaptive => {
+                let total: u64 = ranges
+                    .iter()
+                    .map(|(start, end)| end.saturating_sub(*start))
+                    .fold(0u64, u64::saturating_add);
+                let strategy = if ranges.len() <= 1 || total < ADAPTIVE_PARALLEL_THRESHOLD {
+                    ReadStrategy::Sequential
+                } else {
+                    ReadStrategy::GlobalRayon
+                };
+                self.read_ranges_with_strategy(ranges, strategy)
+            }
+        }
+    }
+
+    /// Reads and processes ranges in parallel, yielding results as they
+    /// complete.
+    ///
+    /// Each range is decoded and passed to `process` along with its index in
+    /// `ranges`. Results arrive in completion order, not input order; use the
+    /// index to restore order if needed. At most one unconsumed result per
+    /// worker is buffered, so workers pause when the caller falls behind.
+    /// Dropping the iterator early stops remaining work.
+    ///
+    /// The work runs on a private pool, sized like rayon's global pool, on a
+    /// thread of its own. Paused workers therefore never hold global pool
+    /// threads, and the caller can use rayon, including
+    /// [`read_ranges`](Self::read_ranges), while iterating.
+    pub fn into_stream_pipeline<T, F>(
+        &self,
+        ranges: Vec<(u64, u64)>,
+        process: F,
+    ) -> impl Iterator<Item = Result<T, Error>>
+    where
+        T: Send + 'static,
+        F: Fn(usize, Vec<u8>) -> T + Sync + Send + 'static,
+    {
+        let threads = rayon::current_num_threads();
+        let (sender, receiver) = std::sync::mpsc::sync_channel(threads);
+        let source = self.source.clone();
+
+        std::thread::spawn(move || {
+            let pool = match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+                Ok(pool) => pool,
+                Err(e) => {
+                    let _ = sender.send(Err(Error::Io(std::io::Error::other(e))));
+                    return;
+                }
+            };
+            pool.install(|| {
+                // A send error means the receiver was dropped; stop early
+                let _ = ranges.into_par_iter().enumerate().try_for_each_with(
+                    sender,
+                    |sender, (index, (start, end))| {
+                        let result = source
+                            .read_range(start, end)
+                            .map(|data| process(index, data));
+                        sender.send(result)
+                    },
+                );
+            });
+        });
+
+        receiver.into_iter()
+    }
+
+    /// Reads multiple ranges in parallel with at most `max_concurrent_opens`
+    /// reads touching the source at a time.
+    ///
+    /// Each range opens its own handle on the source, as in `read_ranges`.
+    /// On spinning disks or rate-limited network shares, many simultaneous
+    /// handles seeking in parallel cause contention; this queues the
+    /// remaining ranges until a slot frees up. A slot is held from opening
+    /// the source until the range is fully decoded. A limit of `0` is
+    /// treated as `1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the reads fail.
+    pub fn read_ranges_with_io_limit(
+        &self,
+        ranges: &[(u64, u64)],
+        max_concurrent_opens: usize,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        let limit = IoLimit::new(max_concurrent_opens.max(1));
+        ranges
+            .par_iter()
+            .map(|(start, end)| limit.run(|| self.source.read_range(*start, *end)))
+            .collect()
+    }
+
+    /// Reads multiple ranges in parallel while bounding peak memory.
+    ///
+    /// Ranges are split, in order, into batches whose combined length stays
+    /// under `max_concurrent_bytes`. Batches run one after another; ranges
+    /// within a batch are decoded in parallel. A single range larger than the
+    /// budget runs in a batch of its own. Results are returned in the same
+    /// order as `ranges`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the reads fail.
+    pub fn read_ranges_bounded(
+        &self,
+        ranges: &[(u64, u64)],
+        max_concurrent_bytes: u64,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        let mut results = Vec::with_capacity(ranges.len());
+        let mut batch_start = 0;
+        let mut batch_bytes = 0u64;
+
+        for (i, (start, end)) in ranges.iter().enumerate() {
+            let range_bytes = end.saturating_sub(*start);
+            // Ends past the archive are clamped by the reads, so lengths
+            // here can be arbitrarily large
+            if i > batch_start && batch_bytes.saturating_add(range_bytes) > max_concurrent_bytes {
+                results.extend(self.read_ranges(&ranges[batch_start..i])?);
+                batch_start = i;
+                batch_bytes = 0;
+            }
+            batch_bytes = batch_bytes.saturating_add(range_bytes);
+        }
+        results.extend(self.read_ranges(&ranges[batch_start..])?);
+
+        Ok(results)
+    }
+}
+
+/// A [`ParallelDecoder`] that splits single reads across its own thread pool.
+///
+/// `ParallelDecoder` runs on rayon's global pool, which it shares with the
+/// rest of the application. This type builds a dedicated pool on first use,
+/// so large reads neither wait behind nor slow down other rayon work. The
+/// pool is kept for later calls and rebuilt only if the thread count changes.
+pub struct SelfParallelDecoder {
+    inner: ParallelDecoder,
+    seek_table: SeekTable,
+    pool: Mutex<Option<(usize, Arc<rayon::ThreadPool>)>>,
+}
+
+impl SelfParallelDecoder {
+    /// Wraps a parallel decoder, reading its seek table once up front.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the seek table cannot be read.
+    pub fn new(inner: ParallelDecoder) -> Result<Self, Error> {
+        let seek_table = inner.seek_table()?;
+        Ok(Self {
+            inner,
+            seek_table,
+            pool: Mutex::new(None),
+        })
+    }
+
+    #[must_use]
+    pub fn inner(&self) -> &ParallelDecoder {
+        &self.inner
+    }
+
+    /// Reads `start..end` by decoding up to `threads` frame-aligned pieces in
+    /// parallel on this decoder's own pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `end < start`, if the thread pool cannot be
+    /// created, or if any of the reads fail.
+    pub fn read_range_parallel_self(
+        &self,
+        start: u64,
+        end: u64,
+        threads: usize,
+    ) -> Result<Vec<u8>, Error> {
+        if end < start {
+            return Err(Error::Format(
+                "End offset cannot be less than start offset".to_string(),
+            ));
+        }
+        check_in_bounds(start, end, self.seek_table.size())?;
+        let end = end.min(self.seek_table.size());
+        if start >= end {
+            return Ok(Vec::new());
+        }
+
+        let table = &self.seek_table.inner;
+        let start_frame = table.frame_index_decomp(start);
+        let end_frame = table.frame_index_decomp(end - 1);
+        let frames = end_frame - start_frame + 1;
+        let threads = threads.max(1);
+        let pieces_len = u32::try_from(threads).unwrap_or(u32::MAX).min(frames);
+
+        // Split the frames into contiguous groups, one per piece
+        let mut pieces = Vec::with_capacity(pieces_len as usize);
+        let mut piece_start = start;
+        for piece in 1..=pieces_len {
+            // At most `frames`, but the product can overflow `u32`
+            let piece_frames = u64::from(frames) * u64::from(piece) / u64::from(pieces_len);
+            let piece_frames = u32::try_from(piece_frames)
+                .map_err(|_| Error::Format("Frame index too large".to_string()))?;
+            let last_frame = start_frame + piece_frames - 1;
+            let piece_end = table
+                .frame_end_decomp(last_frame)
+                .map_err(Error::from)?
+                .min(end);
+            pieces.push((piece_start, piece_end));
+            piece_start = piece_end;
+        }
+
+        let pool = self.pool(threads)?;
+        let parts = pool.install(|| self.inner.read_ranges(&pieces))?;
+        Ok(parts.concat())
+    }
+
+    fn pool(&self, threads: usize) -> Result<Arc<rayon::ThreadPool>, Error> {
+        let mut cached = self
+            .pool
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some((size, pool)) = cached.as_ref() {
+            if *size == threads {
+                return Ok(Arc::clone(pool));
+            }
+        }
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+        let pool = Arc::new(pool);
+        *cached = Some((threads, Arc::clone(&pool)));
+        Ok(pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::Encoder;
+
+    #[test]
+    fn test_from_bytes() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap();
+        assert_eq!(decoder.size(), data.len() as u64);
+        assert_eq!(decoder.frame_count(), 8);
+
+        let ranges = [(0, 100), (500, 1500), (4000, 4096)];
+        let results = decoder.read_ranges(&ranges).unwrap();
+        for ((start, end), result) in ranges.iter().zip(results) {
+            let expected = &data[usize::try_from(*start).unwrap()..usize::try_from(*end).unwrap()];
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_with_pool() {
+        let path =
+            std::env::temp_dir().join(format!("seekable-zstd-pool-{}.szst", std::process::id()));
+        let data: Vec<u8> = (0..64 * 1024u32).map(|i| (i % 251) as u8).collect();
+        let mut encoder = Encoder::with_frame_size(File::create(&path).unwrap(), 1024).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let ranges: Vec<(u64, u64)> = (0..1000u64)
+            .map(|i| {
+                let start = i * 7919 % 65_000;
+                (start, start + i % 700)
+            })
+            .collect();
+        let pooled = ParallelDecoder::with_pool(&path).unwrap();
+        let reopened = ParallelDecoder::open(&path).unwrap();
+        assert_eq!(pooled.size(), reopened.size());
+        assert_eq!(
+            pooled.read_ranges(&ranges).unwrap(),
+            reopened.read_ranges(&ranges).unwrap()
+        );
+        // Frame reads go through the same kept decoders
+        assert_eq!(
+            pooled
+                .read_frames_with_transform(&[3], |frame| Ok(frame.to_vec()))
+                .unwrap(),
+            vec![data[3072..4096].to_vec()]
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_with_warm_cache() {
+        let path =
+            std::env::temp_dir().join(format!("seekable-zstd-warm-{}.szst", std::process::id()));
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut encoder = Encoder::with_frame_size(File::create(&path).unwrap(), 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        assert!(matches!(
+            ParallelDecoder::with_warm_cache(&path, 4095),
+            Err(Error::Format(_))
+        ));
+        let cold = ParallelDecoder::open(&path).unwrap();
+        let warm = ParallelDecoder::with_warm_cache(&path, 4096).unwrap();
+
+        // Reads no longer touch the file
+        std::fs::remove_file(&path).unwrap();
+        assert!(cold.read_ranges(&[(0, 1)]).is_err());
+        assert_eq!(warm.size(), 4096);
+        assert_eq!(warm.frame_count(), 8);
+
+        let ranges = [(0, 100), (500, 1500), (4000, 5000), (4096, 4096), (7, 7)];
+        let results = warm.read_ranges(&ranges).unwrap();
+        for ((start, end), result) in ranges.iter().zip(results) {
+            let start = usize::try_from(*start).unwrap().min(data.len());
+            let end = usize::try_from(*end).unwrap().min(data.len());
+            assert_eq!(result, data[start..end.max(start)]);
+        }
+        assert!(warm.read_ranges(&[(10, 5)]).is_err());
+        assert!(matches!(
+            warm.read_ranges(&[(5000, 6000)]),
+            Err(Error::OutOfBounds {
+                offset: 5000,
+                size: 4096
+            })
+        ));
+
+        let frames = warm
+            .read_frames_with_transform(&[7, 0], |frame| Ok(frame.len()))
+            .unwrap();
+        assert_eq!(frames, vec![512, 512]);
+        assert!(warm.read_frames_with_transform(&[8], |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn test_open_lazy() {
+        let path =
+            std::env::temp_dir().join(format!("seekable-zstd-lazy-{}.szst", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // Nothing is read until first use, so a missing file is not an error yet
+        let decoder = ParallelDecoder::open_lazy(&path);
+        assert!(!decoder.is_initialized());
+        assert!(decoder.read_ranges(&[(0, 4)]).is_err());
+        assert_eq!(decoder.size(), 0);
+        assert!(!decoder.is_initialized());
+
+        let mut encoder = Encoder::with_frame_size(File::create(&path).unwrap(), 4).unwrap();
+        encoder.write_all(b"lazily opened").unwrap();
+        encoder.finish().unwrap();
+
+        assert_eq!(decoder.frame_count(), 4);
+        assert!(decoder.is_initialized());
+        assert_eq!(decoder.size(), 13);
+        assert_eq!(
+            decoder.read_ranges(&[(0, 6)]).unwrap(),
+            vec![b"lazily".to_vec()]
+        );
+
+        let decoder = ParallelDecoder::open_lazy(&path);
+        decoder.read_ranges(&[(7, 13)]).unwrap();
+        assert!(decoder.is_initialized());
+        assert!(ParallelDecoder::open(&path).unwrap().is_initialized());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_range_async_write() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap();
+        let mut output = Vec::new();
+        let written = decoder
+            .read_range_async_write(100, 3000, &mut output)
+            .await
+            .unwrap();
+        assert_eq!(written, 2900);
+        assert_eq!(output, &data[100..3000]);
+
+        let mut output = Vec::new();
+        assert!(decoder
+            .read_range_async_write(3000, 100, &mut output)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_read_frames_with_transform() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap();
+        let firsts = decoder
+            .read_frames_with_transform(&[7, 0, 3], |frame| Ok((frame.len(), frame[0])))
+            .unwrap();
+        assert_eq!(
+            firsts,
+            vec![(512, data[3584]), (512, data[0]), (512, data[1536])]
+        );
+
+        assert!(decoder
+            .read_frames_with_transform(&[8], |frame| Ok(frame.len()))
+            .is_err());
+        assert!(decoder
+            .read_frames_with_transform(&[0], |_| Err::<(), _>(Error::Format("bad".into())))
+            .is_err());
+    }
+
+    #[test]
+    fn test_read_ranges_with_strategy() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap();
+        let ranges = [(0, 100), (500, 1500), (4000, 4096)];
+        let expected = decoder.read_ranges(&ranges).unwrap();
+        for strategy in [
+            ReadStrategy::GlobalRayon,
+            ReadStrategy::LocalThreadPool(2),
+            ReadStrategy::Sequential,
+            ReadStrategy::Adaptive,
+        ] {
+            assert_eq!(
+                decoder
+                    .read_ranges_with_strategy(&ranges, strategy)
+                    .unwrap(),
+                expected
+            );
+        }
+        assert!(decoder
+            .read_ranges_with_strategy(&[(10, 5)], ReadStrategy::Sequential)
+            .is_err());
+        // Ends past the archive are clamped, so their lengths must not overflow
+        assert_eq!(
+            decoder
+                .read_ranges_with_strategy(&[(0, u64::MAX), (0, u64::MAX)], ReadStrategy::Adaptive)
+                .unwrap(),
+            vec![data.clone(), data]
+        );
+    }
+
+    #[test]
+    fn test_from_reader_pool() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let readers: Vec<Box<dyn ReadSeek + Send>> = (0..2)
+            .map(|_| Box::new(Cursor::new(buffer.clone())) as Box<dyn ReadSeek + Send>)
+            .collect();
+        let decoder = ParallelDecoder::from_reader_pool(readers).unwrap();
+        assert_eq!(decoder.size(), data.len() as u64);
+        assert_eq!(decoder.frame_count(), 8);
+
+        // More ranges than readers, so workers wait for returned readers
+        let ranges: Vec<(u64, u64)> = (0..16).map(|i| (i * 256, (i + 1) * 256)).collect();
+        let results = decoder.read_ranges(&ranges).unwrap();
+        assert_eq!(results.concat(), data);
+        assert!(decoder.read_ranges(&[(5000, 6000)]).is_err());
+        assert_eq!(
+            decoder.read_ranges(&[(0, 4)]).unwrap(),
+            vec![data[..4].to_vec()]
+        );
+
+        assert!(ParallelDecoder::from_reader_pool(Vec::new()).is_err());
+
+        // Every reader must hold the same archive
+        let mut other = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut other, 512).unwrap();
+        encoder.write_all(&data[..1000]).unwrap();
+        encoder.finish().unwrap();
+        let readers: Vec<Box<dyn ReadSeek + Send>> =
+            vec![Box::new(Cursor::new(buffer)), Box::new(Cursor::new(other))];
+        assert!(matches!(
+            ParallelDecoder::from_reader_pool(readers),
+            Err(Error::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_ranges_bounded() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap();
+        let ranges: Vec<(u64, u64)> = (0..16).map(|i| (i * 256, (i + 1) * 256)).collect();
+
+        let expected = decoder.read_ranges(&ranges).unwrap();
+        // 768 bytes fits three ranges per batch; a 1-byte budget forces one
+        // range per batch.
+        assert_eq!(decoder.read_ranges_bounded(&ranges, 768).unwrap(), expected);
+        assert_eq!(decoder.read_ranges_bounded(&ranges, 1).unwrap(), expected);
+        assert!(decoder.read_ranges_bounded(&[], 1024).unwrap().is_empty());
+        assert_eq!(
+            decoder
+                .read_ranges_bounded(&[(0, u64::MAX), (0, u64::MAX)], 8192)
+                .unwrap(),
+            vec![data.clone(), data]
+        );
+    }
+
+    #[test]
+    fn test_read_ranges_with_io_limit() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap();
+        let ranges: Vec<(u64, u64)> = (0..16).map(|i| (i * 256, (i + 1) * 256)).collect();
+
+        let expected = decoder.read_ranges(&ranges).unwrap();
+        assert_eq!(
+            decoder.read_ranges_with_io_limit(&ranges, 2).unwrap(),
+            expected
+        );
+        assert_eq!(
+            decoder.read_ranges_with_io_limit(&ranges, 0).unwrap(),
+            expected
+        );
+
+        // A panic inside the limit still returns its permit
+        let limit = IoLimit::new(1);
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            limit.run(|| panic!("read"));
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(limit.run(|| 7), 7);
+    }
+
+    #[test]
+    fn test_into_stream_pipeline() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap();
+        let ranges: Vec<(u64, u64)> = (0..16).map(|i| (i * 256, (i + 1) * 256)).collect();
+
+        let mut sums: Vec<(usize, u64)> = decoder
+            .into_stream_pipeline(ranges, |index, bytes| {
+                (index, bytes.iter().map(|b| u64::from(*b)).sum())
+            })
+            .collect::<Result<_, _>>()
+            .unwrap();
+        sums.sort_unstable();
+
+        let expected: Vec<(usize, u64)> = data
+            .chunks(256)
+            .map(|chunk| chunk.iter().map(|b| u64::from(*b)).sum())
+            .enumerate()
+            .collect();
+        assert_eq!(sums, expected);
+
+        // Stopping early must not hang the pool
+        let mut results = decoder.into_stream_pipeline(vec![(0, 100); 64], |_, bytes| bytes.len());
+        assert_eq!(results.next().unwrap().unwrap(), 100);
+
+        // A slow consumer that itself uses the global pool must not deadlock
+        let ranges: Vec<(u64, u64)> = vec![(0, 4096); rayon::current_num_threads() * 4];
+        for result in decoder.into_stream_pipeline(ranges, |_, bytes| bytes.len()) {
+            assert_eq!(result.unwrap(), 4096);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            assert_eq!(decoder.read_ranges(&[(0, 8), (8, 16)]).unwrap().len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_read_range_parallel_self() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder =
+            SelfParallelDecoder::new(ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap())
+                .unwrap();
+        for (start, end, threads) in [(0, 4096, 4), (100, 3000, 3), (700, 800, 8), (4000, 9000, 2)]
+        {
+            let result = decoder
+                .read_range_parallel_self(start, end, threads)
+                .unwrap();
+            let end = end.min(4096);
+            assert_eq!(
+                result,
+                &data[usize::try_from(start).unwrap()..usize::try_from(end).unwrap()]
+            );
+        }
+        assert!(decoder
+            .read_range_parallel_self(10, 10, 2)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            decoder.read_range_parallel_self(5000, 6000, 2),
+            Err(Error::OutOfBounds { .. })
+        ));
+    }
 }
@@ -0,0 +1,134 @@
+use crate::error::Error;
+use crate::parallel::ParallelDecoder;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::{Condvar, Mutex, MutexGuard};
+
+/// Scheduling state shared by all callers of a `PriorityParallelDecoder`.
+#[derive(Default)]
+struct Queue {
+    /// Waiting requests, highest priority first, then oldest first.
+    pending: BinaryHeap<(u8, Reverse<u64>)>,
+    next_ticket: u64,
+    running: bool,
+    /// Priorities in the order requests started, for tests.
+    #[cfg(test)]
+    served: Vec<u8>,
+}
+
+/// A [`ParallelDecoder`] that serves concurrent callers in priority order.
+///
+/// Each `read_ranges_priority` call is one request. Requests run one at a
+/// time, each using the whole rayon pool. When a request finishes, the
+/// waiting request with the highest priority runs next; equal priorities run
+/// in arrival order. A running request is never preempted.
+pub struct PriorityParallelDecoder {
+    inner: ParallelDecoder,
+    queue: Mutex<Queue>,
+    ready: Condvar,
+}
+
+/// Marks the queue idle when a request finishes, even if it panicked.
+struct RunningGuard<'a> {
+    decoder: &'a PriorityParallelDecoder,
+}
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        self.decoder.lock().running = false;
+        self.decoder.ready.notify_all();
+    }
+}
+
+impl PriorityParallelDecoder {
+    #[must_use]
+    pub fn new(inner: ParallelDecoder) -> Self {
+        Self {
+            inner,
+            queue: Mutex::new(Queue::default()),
+            ready: Condvar::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn inner(&self) -> &ParallelDecoder {
+        &self.inner
+    }
+
+    /// Reads multiple ranges in parallel once all higher-priority requests
+    /// have been served.
+    ///
+    /// Higher `priority` values are served first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the reads fail.
+    pub fn read_ranges_priority(
+        &self,
+        ranges: &[(u64, u64)],
+        priority: u8,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        let mut queue = self.lock();
+        let ticket = (priority, Reverse(queue.next_ticket));
+        queue.next_ticket += 1;
+        queue.pending.push(ticket);
+
+        while queue.running || queue.pending.peek() != Some(&ticket) {
+            queue = self
+                .ready
+                .wait(queue)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        queue.pending.pop();
+        queue.running = true;
+        #[cfg(test)]
+        queue.served.push(priority);
+        drop(queue);
+
+        let _guard = RunningGuard { decoder: self };
+        self.inner.read_ranges(ranges)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::Encoder;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_priority_order() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 512).unwrap();
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder =
+            PriorityParallelDecoder::new(ParallelDecoder::from_bytes(Arc::new(buffer)).unwrap());
+
+        // Hold the queue so both requests are pending before either runs
+        decoder.lock().running = true;
+        std::thread::scope(|scope| {
+            for priority in [1u8, 9] {
+                let (decoder, data) = (&decoder, &data);
+                scope.spawn(move || {
+                    let result = decoder.read_ranges_priority(&[(0, 10)], priority).unwrap();
+                    assert_eq!(result[0], &data[..10]);
+                });
+            }
+            while decoder.lock().pending.len() < 2 {
+                std::thread::yield_now();
+            }
+            drop(RunningGuard { decoder: &decoder });
+        });
+
+        assert_eq!(decoder.lock().served, vec![9, 1]);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::error::Error;
+use std::io::{Read, Seek};
+use std::ops::Range;
+
+/// Where one frame sits in the archive, from [`SeekTable::frames`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FrameInfo {
+    pub index: u64,
+    /// Decompressed offset of the frame's first byte.
+    pub decomp_start: u64,
+    /// Decompressed offset just past the frame's last byte.
+    pub decomp_end: u64,
+    /// Offset of the frame within the compressed archive.
+    pub comp_start: u64,
+    /// Compressed size of the frame in bytes.
+    pub comp_size: u64,
+}
+
+/// A parsed seek table, detached from the archive it describes.
+///
+/// Parsing the seek table is the main cost of opening a [`Decoder`]. Callers
+/// that open the same archive repeatedly can parse it once, cache it (for
+/// example via [`to_bytes`](Self::to_bytes)), and hand it to
+/// [`Decoder::from_reader_and_seek_table`].
+///
+/// [`Decoder`]: crate::Decoder
+/// [`Decoder::from_reader_and_seek_table`]: crate::Decoder::from_reader_and_seek_table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeekTable {
+    pub(crate) inner: zeekstd::SeekTable,
+}
+
+impl SeekTable {
+    /// Parses the seek table from the footer of a seekable archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader does not end with a valid seek table.
+    pub fn from_reader<R: Read + Seek>(reader: &mut R) -> Result<Self, Error> {
+        let inner = zeekstd::SeekTable::from_seekable(reader).map_err(Error::from)?;
+        Ok(Self { inner })
+    }
+
+    /// Parses a seek table previously produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a valid serialized seek table.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let inner = zeekstd::SeekTable::from_bytes(bytes).map_err(Error::from)?;
+        Ok(Self { inner })
+    }
+
+    /// Serializes the seek table in the same format used in the archive footer.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut serializer = self.inner.clone().into_serializer();
+        let mut bytes = vec![0u8; serializer.encoded_len()];
+        let written = serializer.write_into(&mut bytes);
+        bytes.truncate(written);
+        bytes
+    }
+
+    #[must_use]
+    pub fn frame_count(&self) -> u64 {
+        u64::from(self.inner.num_frames())
+    }
+
+    /// Returns the total decompressed size described by the table.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        let num_frames = self.inner.num_frames();
+        if num_frames == 0 {
+            return 0;
+        }
+        self.inner.frame_end_decomp(num_frames - 1).unwrap_or(0)
+    }
+
+    /// Returns the total compressed size of all frames, excluding the seek
+    /// table itself.
+    #[must_use]
+    pub fn compressed_size(&self) -> u64 {
+        let num_frames = self.inner.num_frames();
+        if num_frames == 0 {
+            return 0;
+        }
+        self.inner.frame_end_comp(num_frames - 1).unwrap_or(0)
+    }
+
+    /// Returns the decompressed size of frame `index`, or `None` if it is out
+    /// of range.
+    #[must_use]
+    pub fn frame_size(&self, index: u64) -> Option<u64> {
+        let index = u32::try_from(index).ok()?;
+        self.inner.frame_size_decomp(index).ok()
+    }
+
+    /// Returns the decompressed byte range covered by frame `index`, or
+    /// `None` if it is out of range.
+    #[must_use]
+    pub fn frame_range(&self, index: u64) -> Option<Range<u64>> {
+        let index = u32::try_from(index).ok()?;
+        let start = self.inner.frame_start_decomp(index).ok()?;
+        let end = self.inner.frame_end_decomp(index).ok()?;
+        Some(start..end)
+    }
+
+    /// Returns the byte range of frame `index` within the compressed
+    /// archive, or `None` if it is out of range.
+    ///
+    /// Useful for fetching single frames with HTTP range requests.
+    #[must_use]
+    pub fn frame_compressed_range(&self, index: u64) -> Option<Range<u64>> {
+        let index = u32::try_from(index).ok()?;
+        let start = self.inner.frame_start_comp(index).ok()?;
+        let end = self.inner.frame_end_comp(index).ok()?;
+        Some(start..end)
+    }
+
+    /// Returns the position of every frame, in order.
+    ///
+    /// Useful for aligning reads to frame boundaries, so that no frame is
+    /// decompressed only partly, or for building an external index.
+    #[must_use]
+    pub fn frames(&self) -> Vec<FrameInfo> {
+        (0..self.frame_count())
+            .filter_map(|index| {
+                let decomp = self.frame_range(index)?;
+                let comp = self.frame_compressed_range(index)?;
+                Some(FrameInfo {
+                    index,
+                    decomp_start: decomp.start,
+                    decomp_end: decomp.end,
+                    comp_start: comp.start,
+                    comp_size: comp.end - comp.start,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns `true` if both tables describe the same frames.
+    #[must_use]
+    pub fn validate_against(&self, other: &SeekTable) -> bool {
+        self == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::Encoder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::with_frame_size(&mut buffer, 16).unwrap();
+        encoder.write_all(&[3u8; 40]).unwrap();
+        encoder.finish().unwrap();
+
+        let table = SeekTable::from_reader(&mut Cursor::new(&buffer)).unwrap();
+        assert_eq!(table.frame_count(), 3);
+        assert_eq!(table.size(), 40);
+        assert_eq!(table.frame_size(2), Some(8));
+        assert_eq!(table.frame_size(3), None);
+        assert_eq!(table.frame_range(1), Some(16..32));
+        assert_eq!(table.frame_range(3), None);
+        let last = table.frame_compressed_range(2).unwrap();
+        assert_eq!(table.frame_compressed_range(1).unwrap().end, last.start);
+        assert_eq!(last.end, table.compressed_size());
+        assert_eq!(table.frame_compressed_range(3), None);
+        assert!(table.compressed_size() > 0);
+
+        let frames = table.frames();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].index, 1);
+        assert_eq!((frames[1].decomp_start, frames[1].decomp_end), (16, 32));
+        assert_eq!(frames[0].comp_start, 0);
+        assert_eq!(frames[2].comp_start, last.start);
+        assert_eq!(frames[2].comp_size, last.end - last.start);
+
+        let restored = SeekTable::from_bytes(&table.to_bytes()).unwrap();
+        assert!(restored.validate_against(&table));
+        assert!(!restored.validate_against(&SeekTable {
+            inner: zeekstd::SeekTable::new(),
+        }));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::decoder::Decoder;
+use crate::error::Error;
+use std::io::{Read, Seek};
+
+/// A [`Decoder`] for archives where every frame holds one serialized record.
+///
+/// Each frame is decompressed and passed to the deserializer given at
+/// construction, so any format works (JSON, bincode, protobuf) without this
+/// crate depending on it. Iterating yields records in frame order, starting
+/// from the first frame.
+pub struct TypedDecoder<'a, R: Read + Seek, T> {
+    decoder: Decoder<'a, R>,
+    deserialize: fn(&[u8]) -> Result<T, Error>,
+    next_frame: u64,
+}
+
+impl<'a, R: Read + Seek, T> TypedDecoder<'a, R, T> {
+    /// Wraps `decoder`, decoding each frame with `deserialize`.
+    pub fn new(decoder: Decoder<'a, R>, deserialize: fn(&[u8]) -> Result<T, Error>) -> Self {
+        Self {
+            decoder,
+            deserialize,
+            next_frame: 0,
+        }
+    }
+
+    /// Reads and deserializes the record stored in frame `frame_index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `frame_index` is out of range, if decompression
+    /// fails, or the error returned by the deserializer.
+    pub fn read_record(&mut self, frame_index: u64) -> Result<T, Error> {
+        let frame = self.decoder.read_frame(frame_index)?;
+        (self.deserialize)(&frame)
+    }
+
+    /// Returns the number of records, one per frame.
+    #[must_use]
+    pub fn record_count(&self) -> u64 {
+        self.decoder.frame_count()
+    }
+
+    /// Returns the underlying decoder.
+    #[must_use]
+    pub fn into_inner(self) -> Decoder<'a, R> {
+        self.decoder
+    }
+}
+
+impl<R: Read + Seek, T> Iterator for TypedDecoder<'_, R, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_frame >= self.decoder.frame_count() {
+            return None;
+        }
+        let record = self.read_record(self.next_frame);
+        self.next_frame += 1;
+        Some(record)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.decoder.frame_count() - self.next_frame;
+        let remaining = usize::try_from(remaining).unwrap_or(usize::MAX);
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::Encoder;
+    use std::io::Cursor;
+
+    fn parse_number(frame: &[u8]) -> Result<u32, Error> {
+        std::str::from_utf8(frame)
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .ok_or_else(|| Error::Format("not a number".to_string()))
+    }
+
+    #[test]
+    fn test_typed_decoder() {
+        let mut buffer = Vec::new();
+        let mut encoder = Encoder::new(&mut buffer).unwrap();
+        let records: [&[u8]; 4] = [b"7", b"42", b"oops", b"1000"];
+        encoder.compress_blocks(records).unwrap();
+        encoder.finish().unwrap();
+
+        let decoder = Decoder::new(Cursor::new(buffer)).unwrap();
+        let mut typed = TypedDecoder::new(decoder, parse_number);
+        assert_eq!(typed.record_count(), 4);
+        assert_eq!(typed.read_record(3).unwrap(), 1000);
+        assert!(typed.read_record(4).is_err());
+
+        let results: Vec<Result<u32, Error>> = typed.collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap(), &7);
+        assert_eq!(results[1].as_ref().unwrap(), &42);
+        assert!(results[2].is_err());
+        assert_eq!(results[3].as_ref().unwrap(), &1000);
+    }
+}
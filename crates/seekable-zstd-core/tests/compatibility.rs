@@ -0,0 +1,85 @@
+//! Format stability check against a committed golden archive.
+//!
+//! `tests/fixtures/golden_v1.szst` holds the exact bytes this crate produced
+//! for [`golden_input`] with the pinned parameters below. If encoding the
+//! same input now produces different bytes (for example after a zeekstd or
+//! libzstd update), the output format has drifted and this test fails.
+//!
+//! Archives written by older versions must stay readable, so a failure is a
+//! compatibility decision, not something to paper over. After confirming the
+//! new output still decodes the old fixture and vice versa, regenerate the
+//! fixture and note the change in `CHANGELOG.md`:
+//!
+//! ```sh
+//! SEEKABLE_ZSTD_REGENERATE_GOLDEN=1 cargo test -p seekable-zstd-core --test compatibility
+//! ```
+
+use seekable_zstd_core::{Decoder, Encoder};
+use std::io::Cursor;
+use std::path::PathBuf;
+
+const FRAME_SIZE: usize = 1024;
+const LEVEL: i32 = 3;
+
+fn golden_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/golden_v1.szst")
+}
+
+/// 2500 bytes of deterministic, partly compressible text spanning three frames.
+fn golden_input() -> Vec<u8> {
+    let words: [&[u8]; 8] = [
+        b"seekable ",
+        b"zstd ",
+        b"frame ",
+        b"archive ",
+        b"offset ",
+        b"range ",
+        b"table ",
+        b"\n",
+    ];
+    let mut state = 0x2545_f491_u32;
+    let mut input = Vec::with_capacity(2500);
+    while input.len() < 2500 {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        input.extend_from_slice(words[(state % 8) as usize]);
+    }
+    input.truncate(2500);
+    input
+}
+
+fn encode(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut encoder = Encoder::new_with_options(&mut output, FRAME_SIZE, LEVEL).unwrap();
+    encoder.write_all(input).unwrap();
+    encoder.finish().unwrap();
+    output
+}
+
+#[test]
+fn encoder_output_matches_golden_fixture() {
+    let encoded = encode(&golden_input());
+
+    if std::env::var_os("SEEKABLE_ZSTD_REGENERATE_GOLDEN").is_some() {
+        std::fs::write(golden_path(), &encoded).unwrap();
+    }
+
+    let golden = std::fs::read(golden_path()).unwrap();
+    assert!(
+        encoded == golden,
+        "encoder output differs from {}; see the module docs before regenerating",
+        golden_path().display()
+    );
+}
+
+#[test]
+fn golden_fixture_decodes() {
+    let golden = std::fs::read(golden_path()).unwrap();
+    let mut decoder = Decoder::new(Cursor::new(golden)).unwrap();
+    assert_eq!(decoder.frame_count(), 3);
+    assert_eq!(
+        decoder.read_range(0, decoder.size()).unwrap(),
+        golden_input()
+    );
+}
@@ -4,6 +4,17 @@ class Reader:
     def size(self) -> int: ...
     @property
     def frame_count(self) -> int: ...
+    @property
+    def metadata(self) -> dict[str, int | float]: ...
+    def seek_table(self) -> list[dict[str, int]]: ...
+    def prefetch(self, start: int, end: int) -> None:
+        """Queue ``start..end`` for decompression in the background.
+
+        One worker thread per reader serves the queue in order. At most 64
+        unread ranges are kept; prefetching more drops the oldest, which
+        ``read_range`` then reads directly.
+        """
+    def is_prefetched(self, start: int, end: int) -> bool: ...
     def read_range(self, start: int, end: int) -> bytes: ...
     def read_ranges(self, ranges: list[tuple[int, int]]) -> list[bytes]: ...
     def __enter__(self) -> Reader: ...
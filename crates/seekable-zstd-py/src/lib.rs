@@ -1,19 +1,109 @@
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
-use seekable_zstd_core::ParallelDecoder;
+use pyo3::types::{PyBytes, PyDict, PyList};
+use seekable_zstd_core::{ArchiveMetadata, Error, ParallelDecoder};
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex, OnceLock, PoisonError};
+
+/// Most prefetched ranges a `Reader` holds before dropping the oldest.
+const MAX_PREFETCHED: usize = 64;
+
+/// Raises `IOError` with the full cause chain as its message.
+fn to_py_err(err: Error) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyIOError, _>(err.display_chain())
+}
+
+/// A range being decompressed in the background by `Reader.prefetch`.
+#[derive(Default)]
+struct Prefetch {
+    result: Mutex<Option<Result<Vec<u8>, Error>>>,
+    done: Condvar,
+}
+
+impl Prefetch {
+    fn is_done(&self) -> bool {
+        self.result
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some()
+    }
+
+    /// Blocks until the background read finishes and takes its result.
+    fn wait(&self) -> Result<Vec<u8>, Error> {
+        let mut result = self.result.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(result) = result.take() {
+                return result;
+            }
+            result = self
+                .done
+                .wait(result)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+/// A prefetch request for the background worker.
+type PrefetchJob = ((u64, u64), Arc<Prefetch>);
+
+/// Prefetched ranges that have not been read yet, oldest first, and the
+/// queue of the worker thread decompressing them.
+#[derive(Default)]
+struct Prefetches {
+    pending: HashMap<(u64, u64), Arc<Prefetch>>,
+    order: VecDeque<(u64, u64)>,
+    worker: Option<Sender<PrefetchJob>>,
+}
+
+impl Prefetches {
+    fn take(&mut self, range: (u64, u64)) -> Option<Arc<Prefetch>> {
+        let prefetch = self.pending.remove(&range)?;
+        self.order.retain(|pending| *pending != range);
+        Some(prefetch)
+    }
+}
+
+/// Starts the thread that serves a reader's prefetch queue.
+///
+/// It exits once the reader, and with it the sender, is dropped.
+fn spawn_prefetch_worker(inner: ParallelDecoder) -> Sender<PrefetchJob> {
+    let (sender, receiver) = mpsc::channel::<PrefetchJob>();
+    std::thread::spawn(move || {
+        for ((start, end), prefetch) in receiver {
+            // Evicted before its turn; nobody is waiting for it
+            if Arc::strong_count(&prefetch) == 1 {
+                continue;
+            }
+            let result = inner
+                .read_ranges(&[(start, end)])
+                .map(|mut results| results.pop().unwrap_or_default());
+            *prefetch
+                .result
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = Some(result);
+            prefetch.done.notify_all();
+        }
+    });
+    sender
+}
 
 #[pyclass]
 struct Reader {
     inner: ParallelDecoder,
+    metadata: OnceLock<ArchiveMetadata>,
+    prefetched: Mutex<Prefetches>,
 }
 
 #[pymethods]
 impl Reader {
     #[new]
     fn new(path: &str) -> PyResult<Self> {
-        let inner = ParallelDecoder::open(path)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
-        Ok(Reader { inner })
+        let inner = ParallelDecoder::open(path).map_err(to_py_err)?;
+        Ok(Reader {
+            inner,
+            metadata: OnceLock::new(),
+            prefetched: Mutex::new(Prefetches::default()),
+        })
     }
 
     #[getter]
@@ -26,12 +116,110 @@ impl Reader {
         self.inner.frame_count()
     }
 
+    /// Archive metadata as a dict, read from the seek table once and cached.
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let metadata = match self.metadata.get() {
+            Some(metadata) => *metadata,
+            None => {
+                let metadata = self.inner.metadata().map_err(to_py_err)?;
+                *self.metadata.get_or_init(|| metadata)
+            }
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("size", metadata.size)?;
+        dict.set_item("frame_count", metadata.frame_count)?;
+        dict.set_item("compressed_size", metadata.compressed_size)?;
+        dict.set_item("seek_table_size", metadata.seek_table_size)?;
+        dict.set_item("max_frame_size", metadata.max_frame_size)?;
+        dict.set_item("compression_ratio", metadata.compression_ratio)?;
+        Ok(dict)
+    }
+
+    /// Seek table entries, one dict per frame, with decompressed and
+    /// compressed byte offsets.
+    fn seek_table<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let seek_table = self.inner.seek_table().map_err(to_py_err)?;
+        let entries = PyList::empty(py);
+        for index in 0..seek_table.frame_count() {
+            let (Some(range), Some(compressed)) = (
+                seek_table.frame_range(index),
+                seek_table.frame_compressed_range(index),
+            ) else {
+                continue;
+            };
+            let entry = PyDict::new(py);
+            entry.set_item("frame_index", index)?;
+            entry.set_item("decompressed_start", range.start)?;
+            entry.set_item("decompressed_end", range.end)?;
+            entry.set_item("compressed_start", compressed.start)?;
+            entry.set_item("compressed_end", compressed.end)?;
+            entries.append(entry)?;
+        }
+        Ok(entries)
+    }
+
+    /// Queues `start..end` for decompression in the background.
+    ///
+    /// The next `read_range(start, end)` returns the result, waiting for it
+    /// if the background read has not finished yet. Each prefetched range is
+    /// handed out once. Prefetching a range that is already pending does
+    /// nothing.
+    ///
+    /// One worker thread per reader works through the queue in order, each
+    /// read itself decompressing frames in parallel. At most 64 unread
+    /// ranges are kept; prefetching more drops the oldest, which
+    /// `read_range` then reads directly.
+    fn prefetch(&self, start: u64, end: u64) {
+        let mut prefetched = self
+            .prefetched
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if prefetched.pending.contains_key(&(start, end)) {
+            return;
+        }
+        if prefetched.order.len() >= MAX_PREFETCHED {
+            if let Some(oldest) = prefetched.order.pop_front() {
+                prefetched.pending.remove(&oldest);
+            }
+        }
+        let prefetch = Arc::new(Prefetch::default());
+        prefetched
+            .pending
+            .insert((start, end), Arc::clone(&prefetch));
+        prefetched.order.push_back((start, end));
+
+        let worker = prefetched
+            .worker
+            .get_or_insert_with(|| spawn_prefetch_worker(self.inner.clone()));
+        // The worker only exits once this sender is dropped
+        let _ = worker.send(((start, end), prefetch));
+    }
+
+    /// Whether a prefetch of `start..end` has finished and not been read yet.
+    fn is_prefetched(&self, start: u64, end: u64) -> bool {
+        self.prefetched
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pending
+            .get(&(start, end))
+            .is_some_and(|prefetch| prefetch.is_done())
+    }
+
     fn read_range(&self, py: Python, start: u64, end: u64) -> PyResult<Py<PyBytes>> {
+        let prefetch = self
+            .prefetched
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take((start, end));
+        if let Some(prefetch) = prefetch {
+            let data = py.allow_threads(|| prefetch.wait()).map_err(to_py_err)?;
+            return Ok(PyBytes::new(py, &data).into());
+        }
+
         let range = vec![(start, end)];
-        let results = self
-            .inner
-            .read_ranges(&range)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
+        let results = self.inner.read_ranges(&range).map_err(to_py_err)?;
 
         // Since we only requested one range, we expect one result
         if let Some(data) = results.first() {
@@ -43,10 +231,7 @@ impl Reader {
     }
 
     fn read_ranges(&self, py: Python, ranges: Vec<(u64, u64)>) -> PyResult<Vec<Py<PyBytes>>> {
-        let results = self
-            .inner
-            .read_ranges(&ranges)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
+        let results = self.inner.read_ranges(&ranges).map_err(to_py_err)?;
 
         let mut py_results = Vec::with_capacity(results.len());
         for data in results {
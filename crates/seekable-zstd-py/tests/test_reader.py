@@ -1,4 +1,5 @@
 import os
+import time
 
 import pytest
 from seekable_zstd import Reader
@@ -39,3 +40,83 @@ def test_context_manager():
         assert reader.size == 11
         data = reader.read_range(0, 5)
         assert data == b"Hello"
+
+
+def test_metadata():
+    fixture_path = os.path.join(os.path.dirname(__file__), "../../../tests/fixtures/hello.szst")
+    fixture_path = os.path.abspath(fixture_path)
+
+    if not os.path.exists(fixture_path):
+        pytest.skip(f"Fixture not found at {fixture_path}")
+
+    reader = Reader(fixture_path)
+    metadata = reader.metadata
+    assert metadata["size"] == 11
+    assert metadata["frame_count"] == reader.frame_count
+    archive_size = metadata["compressed_size"] + metadata["seek_table_size"]
+    assert archive_size == os.path.getsize(fixture_path)
+    assert metadata["compression_ratio"] > 0
+
+
+def test_seek_table():
+    fixture_path = os.path.join(os.path.dirname(__file__), "../../../tests/fixtures/hello.szst")
+    fixture_path = os.path.abspath(fixture_path)
+
+    if not os.path.exists(fixture_path):
+        pytest.skip(f"Fixture not found at {fixture_path}")
+
+    reader = Reader(fixture_path)
+    entries = reader.seek_table()
+    assert len(entries) == reader.frame_count
+    assert entries[0]["frame_index"] == 0
+    assert entries[0]["decompressed_start"] == 0
+    assert entries[0]["compressed_start"] == 0
+    assert entries[-1]["decompressed_end"] == reader.size
+    assert entries[-1]["compressed_end"] == reader.metadata["compressed_size"]
+
+
+def test_prefetch():
+    fixture_path = os.path.join(os.path.dirname(__file__), "../../../tests/fixtures/hello.szst")
+    fixture_path = os.path.abspath(fixture_path)
+
+    if not os.path.exists(fixture_path):
+        pytest.skip(f"Fixture not found at {fixture_path}")
+
+    reader = Reader(fixture_path)
+    assert not reader.is_prefetched(0, 5)
+    reader.prefetch(0, 5)
+    assert reader.read_range(0, 5) == b"Hello"
+    # A prefetched range is handed out once
+    assert not reader.is_prefetched(0, 5)
+
+    reader.prefetch(6, 11)
+    deadline = time.monotonic() + 5
+    while not reader.is_prefetched(6, 11) and time.monotonic() < deadline:
+        time.sleep(0.01)
+    assert reader.is_prefetched(6, 11)
+    assert reader.read_range(6, 11) == b"World"
+
+
+def test_prefetch_is_bounded():
+    fixture_path = os.path.join(os.path.dirname(__file__), "../../../tests/fixtures/hello.szst")
+    fixture_path = os.path.abspath(fixture_path)
+
+    if not os.path.exists(fixture_path):
+        pytest.skip(f"Fixture not found at {fixture_path}")
+
+    reader = Reader(fixture_path)
+    # All 66 ranges of "Hello World", more than the 64 a reader keeps, so the
+    # two oldest are dropped
+    ranges = [(start, end) for start in range(11) for end in range(start + 1, 12)]
+    for start, end in ranges:
+        reader.prefetch(start, end)
+
+    deadline = time.monotonic() + 5
+    while not reader.is_prefetched(10, 11) and time.monotonic() < deadline:
+        time.sleep(0.01)
+    assert reader.is_prefetched(10, 11)
+    assert reader.is_prefetched(0, 3)
+    assert not reader.is_prefetched(0, 1)
+    assert not reader.is_prefetched(0, 2)
+    # Dropped ranges are still read, just not from the prefetch
+    assert reader.read_range(0, 1) == b"H"
@@ -2,4 +2,16 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
-pub use seekable_zstd_core::{Decoder, Encoder, Error, ParallelDecoder, Result};
+pub use seekable_zstd_core::{
+    AppendValidation, ArchiveMetadata, AtomicEncoder, Decoder, DecoderStats, DecoderStream,
+    DeduplicatingDecoder, DryRunReport, Encoder, Error, FrameInfo, LazyDecompressedRange,
+    ParallelDecoder, PriorityParallelDecoder, ReadRangeResult, ReadSeek, ReadStrategy, Result,
+    RevFrameIter, SeekTable, SelfParallelDecoder, TypedDecoder,
+};
+
+#[cfg(unix)]
+pub use seekable_zstd_core::FileLock;
+#[cfg(feature = "xxhash")]
+pub use seekable_zstd_core::FrameChecksum;
+#[cfg(any(feature = "sha2", feature = "blake3", feature = "xxhash"))]
+pub use seekable_zstd_core::HashAlgorithm;